@@ -9,8 +9,9 @@ use serde::Deserialize;
 // - `Reg`: 常见属性。其值解析方式由 `#address-cells` 和 `#size-cells` 决定。
 // - `NodeSeq`: name@... 区分的一组同级同类的连续节点，这个类型要求可变的内存。
 // - `StrSeq`: '\0' 分隔的一组字符串，设备树中一种常见的属性类型，这个类型要求可变的内存。
+// - `Status`: `status` 属性，表示设备是否可用。
 use serde_device_tree::{
-    Dtb, DtbPtr,
+    Dtb, DtbPtr, Status,
     buildin::{Node, NodeSeq, Reg, StrSeq},
     error::Error,
     from_raw_mut,
@@ -43,8 +44,8 @@ fn main() -> Result<(), Error> {
     // 关于 `#[derive(Deserialize)]`，看[这篇文档](https://serde.rs/derive.html)。
     // 关于 `rename` 等 Attribute，看[这篇文档](https://serde.rs/attributes.html)。
     //
-    // 推荐用 `StrSeq<'a>` 替换所有 `&'a str`，即使肯定只有一个字符串。
-    // 后者在 `derive` 时可能引发奇怪的生命周期问题。
+    // 只有一个字符串的属性可以直接解析为 `&'a str`，
+    // 可能包含多个字符串的属性（如 `compatible`）用 `StrSeq<'a>` 映射。
     //
     // 许多外设可能有不止一个，用 @... 区分，用 `NodeSeq` 映射这类节点。
     // 注意！解析器要求这类节点必须连续出现。
@@ -52,7 +53,7 @@ fn main() -> Result<(), Error> {
     #[derive(Deserialize)]
     struct Tree<'a> {
         compatible: StrSeq<'a>,
-        model: StrSeq<'a>,
+        model: &'a str,
         chosen: Option<Chosen<'a>>,
         cpus: Cpus<'a>,
         memory: NodeSeq<'a>,
@@ -62,7 +63,7 @@ fn main() -> Result<(), Error> {
     #[derive(Deserialize)]
     #[serde(rename_all = "kebab-case")]
     struct Chosen<'a> {
-        stdout_path: Option<StrSeq<'a>>,
+        stdout_path: Option<&'a str>,
     }
 
     #[derive(Deserialize)]
@@ -76,17 +77,17 @@ fn main() -> Result<(), Error> {
     #[derive(Deserialize, Debug)]
     struct Cpu<'a> {
         compatible: StrSeq<'a>,
-        device_type: StrSeq<'a>,
-        status: StrSeq<'a>,
+        device_type: &'a str,
+        status: Status<'a>,
         #[serde(rename = "riscv,isa")]
-        isa: StrSeq<'a>,
+        isa: &'a str,
         #[serde(rename = "mmu-type")]
-        mmu: StrSeq<'a>,
+        mmu: &'a str,
     }
 
     #[derive(Deserialize)]
     struct Memory<'a> {
-        device_type: StrSeq<'a>,
+        device_type: &'a str,
         reg: Reg<'a>,
     }
 
@@ -106,11 +107,11 @@ fn main() -> Result<(), Error> {
         let root: Node = from_raw_mut(&dtb).unwrap();
         let t: Tree = root.deserialize();
 
-        println!("model = {:?}", t.model);
+        println!("model = {}", t.model);
         println!("compatible = {:?}", t.compatible);
         if let Some(chosen) = t.chosen {
             if let Some(stdout_path) = chosen.stdout_path {
-                println!("stdout = {:?}", stdout_path);
+                println!("stdout = {}", stdout_path);
             } else {
                 println!("stdout not chosen");
             }
@@ -119,7 +120,8 @@ fn main() -> Result<(), Error> {
 
         // 可以读取同类节点的数量
         println!("number of cpu = {}", t.cpus.cpu.len());
-        for cpu in t.cpus.cpu.iter() {
+        // 只访问可用的节点
+        for cpu in t.cpus.cpu.iter_enabled() {
             println!("cpu@{}: {:?}", cpu.at(), cpu.deserialize::<Cpu>());
         }
 
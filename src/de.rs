@@ -423,8 +423,20 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
     where
         V: de::Visitor<'de>,
     {
-        let _ = (name, variants, visitor);
-        todo!()
+        let _ = (name, variants);
+        // Only unit variants are supported, named by the string value of the property.
+        match self.peek_tag_index()? {
+            Some((Tag::Prop(value_slice, _name_slice), file_index)) => {
+                let value_slice = value_slice.strip_suffix(b"\0").unwrap_or(value_slice);
+                let s =
+                    core::str::from_utf8(value_slice).map_err(|e| Error::utf8(e, *file_index))?;
+                let value = visitor.visit_enum(de::value::BorrowedStrDeserializer::new(s))?;
+                self.eat_tag()?;
+                Ok(value)
+            }
+            Some((_, file_index)) => Err(Error::unexpected_node("enum", *file_index)),
+            None => Err(Error::no_remaining_tags()),
+        }
     }
 
     fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
@@ -0,0 +1,179 @@
+use super::DtError;
+use serde::de;
+
+/// 属性值中一组连续大端序数值的解析器。
+///
+/// 每解析一个数值，就从 `data` 头部取走对应长度的字节，
+/// 用于将 `<0x1 0x2>` 这样的属性解析为元组或定长数组。
+pub(super) struct CellDeserializer<'de, 'b> {
+    pub data: &'b mut &'de [u8],
+    pub prop_len: usize,
+    pub file_index: usize,
+}
+
+/// 元组、定长数组的元素访问器，恰好产生 `len` 个元素。
+pub(super) struct CellAccess<'de, 'b> {
+    pub data: &'b mut &'de [u8],
+    pub len: usize,
+    pub prop_len: usize,
+    pub file_index: usize,
+}
+
+impl<'de> CellDeserializer<'de, '_> {
+    /// 取走 `N` 字节，剩余字节不足时返回错误。
+    fn take<const N: usize>(&mut self, expected: &'static str) -> Result<[u8; N], DtError> {
+        if self.data.len() < N {
+            return Err(DtError::type_mismatch(
+                expected,
+                self.prop_len,
+                self.file_index,
+            ));
+        }
+        let (head, rest) = self.data.split_at(N);
+        *self.data = rest;
+        Ok(head.try_into().unwrap())
+    }
+
+    fn access<'c>(&'c mut self, len: usize) -> CellAccess<'de, 'c> {
+        CellAccess {
+            data: self.data,
+            len,
+            prop_len: self.prop_len,
+            file_index: self.file_index,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for CellDeserializer<'de, '_> {
+    type Error = DtError;
+
+    /// 未指明类型时，按设备树的惯例读取一个 32 位的 cell。
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_u32(visitor)
+    }
+
+    fn deserialize_i8<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_i8(i8::from_be_bytes(self.take("i8")?))
+    }
+
+    fn deserialize_i16<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_i16(i16::from_be_bytes(self.take("i16")?))
+    }
+
+    fn deserialize_i32<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_i32(i32::from_be_bytes(self.take("i32")?))
+    }
+
+    fn deserialize_i64<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_i64(i64::from_be_bytes(self.take("i64")?))
+    }
+
+    fn deserialize_u8<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u8(u8::from_be_bytes(self.take("u8")?))
+    }
+
+    fn deserialize_u16<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u16(u16::from_be_bytes(self.take("u16")?))
+    }
+
+    fn deserialize_u32<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u32(u32::from_be_bytes(self.take("u32")?))
+    }
+
+    fn deserialize_u64<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_u64(u64::from_be_bytes(self.take("u64")?))
+    }
+
+    fn deserialize_tuple<V>(mut self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_seq(self.access(len))
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        self.deserialize_any(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i128 u128 f32 f64 char str string bytes byte_buf option unit unit_struct
+        seq map struct enum identifier
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for CellAccess<'de, '_> {
+    type Error = DtError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.len == 0 {
+            return Ok(None);
+        }
+        self.len -= 1;
+        seed.deserialize(CellDeserializer {
+            data: self.data,
+            prop_len: self.prop_len,
+            file_index: self.file_index,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.len)
+    }
+}
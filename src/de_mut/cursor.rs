@@ -1,7 +1,7 @@
 use super::{BLOCK_LEN, DtError, RefDtb, StructureBlock};
 use core::marker::PhantomData;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(C)]
 pub(super) struct AnyCursor<T: Type = Body>(usize, PhantomData<T>);
 
@@ -11,11 +11,11 @@ pub(super) type PropCursor = AnyCursor<Prop>;
 
 pub(super) trait Type {}
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) struct Body {}
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) struct Title {}
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(super) struct Prop {}
 
 impl Type for Body {}
@@ -253,8 +253,9 @@ impl PropCursor {
             if len_data.as_usize() == BLOCK_LEN {
                 Ok(u32::from_be(data[0].0))
             } else {
-                Err(DtError::buildin_type_parsed_error(
+                Err(DtError::type_mismatch(
                     "u32",
+                    len_data.as_usize(),
                     self.file_index_on(dtb),
                 ))
             }
@@ -1,4 +1,6 @@
+use super::cells::CellAccess;
 use super::cursor::MultiNodeCursor;
+use super::str_seq::StrSeqAccess;
 use super::{BodyCursor, Cursor};
 use super::{DtError, PropCursor, RefDtb, RegConfig};
 
@@ -16,7 +18,12 @@ pub(super) enum ValueCursor {
 #[derive(Clone, Copy)]
 pub(super) struct ValueDeserializer<'de> {
     pub dtb: RefDtb<'de>,
+    /// 当前节点（或属性所在节点）的地址空间格式，由父节点声明。
     pub reg: RegConfig,
+    /// 当前节点（或属性所在节点）为子节点声明的地址空间格式。
+    ///
+    /// 节点的解析器在开始解析结构体时扫描得到，属性的解析器从所在节点继承。
+    pub child_reg: RegConfig,
     pub cursor: ValueCursor,
 }
 
@@ -40,6 +47,16 @@ impl<'de> Deserialize<'de> for ValueDeserializer<'_> {
             where
                 D: de::Deserializer<'de>,
             {
+                // `untagged`、`flatten` 等会先把值缓冲下来，再用 serde 的解析器交给内置类型，
+                // 只有确认是本模块的解析器时才能转换。
+                use core::{any::type_name, mem::size_of};
+                if type_name::<D>() != type_name::<&mut ValueDeserializer>()
+                    || size_of::<D>() != size_of::<&mut ValueDeserializer>()
+                {
+                    return Err(de::Error::custom(
+                        "device tree types must be deserialized from the device tree directly",
+                    ));
+                }
                 Ok(unsafe {
                     *(*(core::ptr::addr_of!(deserializer) as *const _ as *const &ValueDeserializer))
                 })
@@ -60,70 +77,96 @@ impl<'de> Deserialize<'de> for ValueDeserializer<'_> {
 impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
     type Error = DtError;
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    /// 自描述解析，按以下顺序推断值的类型：
+    ///
+    /// - 节点：映射，键为属性名和子节点的全名；
+    /// - 空属性：`true`，这类属性通常表示一个标志；
+    /// - 可打印的 '\0' 结尾字符串：只有一个时为 `str`，否则为字符串序列；
+    /// - 4 字节长的属性：`u32`；
+    /// - 其他属性：字节串。
+    ///
+    /// 字符串的判断先于 `u32`，因此 `"cpu"` 这样恰好 4 字节长的字符串仍是字符串。
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unimplemented!("any")
+        use super::{StructAccess, StructAccessType, Temp};
+        let data = match self.cursor {
+            ValueCursor::Prop(_, cursor) => cursor.data_on(self.dtb),
+            // 标记已跳过 "/" 键，它只供 `Node` 获取自身的光标。
+            ValueCursor::NodeIn(_) | ValueCursor::Body(_) => {
+                self.scan_child_reg()?;
+                return visitor.visit_map(StructAccess {
+                    access_type: StructAccessType::Map(true),
+                    temp: Temp::Uninit,
+                    de: self,
+                });
+            }
+            ValueCursor::Node(_) => unreachable!("Node -> any (Use NodeIn instead)"),
+        };
+        if data.is_empty() {
+            return visitor.visit_bool(true);
+        }
+        match printable_str_count(data) {
+            0 if data.len() == 4 => self.deserialize_u32(visitor),
+            0 => visitor.visit_borrowed_bytes(data),
+            1 => self.deserialize_str(visitor),
+            _ => visitor.visit_seq(StrSeqAccess::new(data)),
+        }
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        if let ValueCursor::Prop(_, cursor) = self.cursor {
-            let val = cursor.map_on(self.dtb, |data| {
-                if data.is_empty() {
-                    true
-                } else {
-                    todo!("&[u8] -> bool")
-                }
-            });
-            return visitor.visit_bool(val);
+        match self.cursor {
+            ValueCursor::Prop(_, cursor) if cursor.map_on(self.dtb, |data| data.is_empty()) => {
+                visitor.visit_bool(true)
+            }
+            _ => Err(self.type_mismatch("bool")),
         }
-        unreachable!("Node -> bool");
     }
 
-    fn deserialize_i8<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("i8")
+        visitor.visit_i8(i8::from_be_bytes(self.prop_bytes("i8")?))
     }
 
-    fn deserialize_i16<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("i16")
+        visitor.visit_i16(i16::from_be_bytes(self.prop_bytes("i16")?))
     }
 
-    fn deserialize_i32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("i32")
+        visitor.visit_i32(i32::from_be_bytes(self.prop_bytes("i32")?))
     }
 
-    fn deserialize_i64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("i64")
+        visitor.visit_i64(i64::from_be_bytes(self.prop_bytes("i64")?))
     }
 
-    fn deserialize_u8<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("u8")
+        visitor.visit_u8(u8::from_be_bytes(self.prop_bytes("u8")?))
     }
 
-    fn deserialize_u16<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("u16")
+        visitor.visit_u16(u16::from_be_bytes(self.prop_bytes("u16")?))
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -133,49 +176,49 @@ impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
         if let ValueCursor::Prop(_, cursor) = self.cursor {
             return visitor.visit_u32(cursor.map_u32_on(self.dtb)?);
         }
-        unreachable!("node -> u32");
+        Err(self.type_mismatch("u32"))
     }
 
-    fn deserialize_u64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("u64")
+        visitor.visit_u64(u64::from_be_bytes(self.prop_bytes("u64")?))
     }
 
     fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("f32")
+        Err(self.type_mismatch("f32"))
     }
 
     fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("f64")
+        Err(self.type_mismatch("f64"))
     }
 
     fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("char")
+        Err(self.type_mismatch("char"))
     }
 
-    fn deserialize_str<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("str");
+        visitor.visit_borrowed_str(self.prop_str("str")?)
     }
 
-    fn deserialize_string<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("string")
+        self.deserialize_str(visitor)
     }
 
     fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -186,14 +229,14 @@ impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
             let data = cursor.data_on(self.dtb);
             return visitor.visit_borrowed_bytes(data);
         }
-        unreachable!("node -> bytes");
+        Err(self.type_mismatch("bytes"))
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("byte_buf")
+        self.deserialize_bytes(visitor)
     }
 
     fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -235,16 +278,15 @@ impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
 
     fn deserialize_newtype_struct<V>(
         self,
-        name: &'static str,
+        _name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        if name == super::VALUE_DESERIALIZER_NAME {
-            return visitor.visit_newtype_struct(self);
-        }
-        unreachable!("unknown newtype struct");
+        // 内置类型通过 `VALUE_DESERIALIZER_NAME` 取得解析器本身，
+        // 其他新类型结构体直接解析其内部的值。
+        visitor.visit_newtype_struct(self)
     }
 
     fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -272,30 +314,47 @@ impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
                             de,
                         })
                     }
-                    _ => unreachable!("seq request on a none seq cursor"),
+                    _ => Err(self.type_mismatch("seq")),
                 }
             }
-            _ => unreachable!("Seq request on a not-node cursor"),
+            ValueCursor::Prop(_, _) => Err(DtError::unexpected_prop("seq", self.file_index_on())),
+            // `NodeSeqItem` 等给出的节点内容不是一组同名节点。
+            _ => Err(self.type_mismatch("seq")),
         }
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("tuple")
+        let ValueCursor::Prop(_, cursor) = self.cursor else {
+            return Err(self.type_mismatch("tuple"));
+        };
+        // 依次从属性值中取出每个元素，要求恰好用完所有字节。
+        let mut data = cursor.data_on(self.dtb);
+        let value = visitor.visit_seq(CellAccess {
+            prop_len: data.len(),
+            data: &mut data,
+            len,
+            file_index: self.file_index_on(),
+        })?;
+        if data.is_empty() {
+            Ok(value)
+        } else {
+            Err(self.type_mismatch("tuple"))
+        }
     }
 
     fn deserialize_tuple_struct<V>(
         self,
         _name: &'static str,
-        _len: usize,
-        _visitor: V,
+        len: usize,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("tuple_struct")
+        self.deserialize_tuple(len, visitor)
     }
 
     fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
@@ -304,17 +363,15 @@ impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
     {
         use super::{StructAccess, StructAccessType, Temp};
         match self.cursor {
-            ValueCursor::NodeIn(_) => visitor.visit_map(StructAccess {
-                access_type: StructAccessType::Map(false),
-                temp: Temp::Uninit,
-                de: self,
-            }),
-            ValueCursor::Body(_) => visitor.visit_map(StructAccess {
-                access_type: StructAccessType::Map(false),
-                temp: Temp::Uninit,
-                de: self,
-            }),
-            ValueCursor::Prop(_, _) => unreachable!("Prop -> map"),
+            ValueCursor::NodeIn(_) | ValueCursor::Body(_) => {
+                self.scan_child_reg()?;
+                visitor.visit_map(StructAccess {
+                    access_type: StructAccessType::Map(false),
+                    temp: Temp::Uninit,
+                    de: self,
+                })
+            }
+            ValueCursor::Prop(_, _) => Err(DtError::unexpected_prop("map", self.file_index_on())),
             ValueCursor::Node(_) => unreachable!("Node -> map (Use NodeIn instead)"),
         }
     }
@@ -330,17 +387,17 @@ impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
     {
         use super::{StructAccess, StructAccessType, Temp};
         match self.cursor {
-            ValueCursor::NodeIn(_) => visitor.visit_map(StructAccess {
-                access_type: StructAccessType::Struct(fields),
-                temp: Temp::Uninit,
-                de: self,
-            }),
-            ValueCursor::Body(_) => visitor.visit_map(StructAccess {
-                access_type: StructAccessType::Struct(fields),
-                temp: Temp::Uninit,
-                de: self,
-            }),
-            ValueCursor::Prop(_, _) => unreachable!("Prop -> struct {_name}"),
+            ValueCursor::NodeIn(_) | ValueCursor::Body(_) => {
+                self.scan_child_reg()?;
+                visitor.visit_map(StructAccess {
+                    access_type: StructAccessType::Struct(fields),
+                    temp: Temp::Uninit,
+                    de: self,
+                })
+            }
+            ValueCursor::Prop(_, _) => {
+                Err(DtError::unexpected_prop("struct", self.file_index_on()))
+            }
             ValueCursor::Node(_) => unreachable!("Node -> struct {_name} (Use NodeIn instead)"),
         }
     }
@@ -349,30 +406,33 @@ impl<'de> de::Deserializer<'de> for &mut ValueDeserializer<'de> {
         self,
         _name: &'static str,
         _variants: &'static [&'static str],
-        _visitor: V,
+        visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("enum")
+        // 只支持单元变体，变体名就是属性的字符串值。
+        let variant = self.prop_str("enum")?;
+        visitor.visit_enum(de::value::BorrowedStrDeserializer::new(variant))
     }
 
-    fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("identifier")
+        visitor.visit_borrowed_str(self.prop_str("identifier")?)
     }
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: de::Visitor<'de>,
     {
-        unreachable!("ignored_any")
+        // 光标的移动由上层的 `StructAccess` 负责，这里无需跳过任何内容。
+        visitor.visit_unit()
     }
 }
 
-impl ValueDeserializer<'_> {
+impl<'de> ValueDeserializer<'de> {
     #[inline]
     pub fn move_on(&mut self) -> super::Cursor {
         if let ValueCursor::Body(ref mut cursor) = self.cursor {
@@ -387,18 +447,460 @@ impl ValueDeserializer<'_> {
         };
         unreachable!("step_n prop cursor");
     }
+    /// 开始解析节点前，扫描节点的属性，取得节点为子节点声明的地址空间格式。
+    ///
+    /// 属性的顺序是任意的，`ranges` 等属性可能出现在 `#address-cells` 之前，因此需要预先扫描。
+    pub fn scan_child_reg(&mut self) -> Result<(), DtError> {
+        let start = match self.cursor {
+            ValueCursor::NodeIn(result) => result.data_cursor,
+            ValueCursor::Body(cursor) => cursor,
+            _ => return Ok(()),
+        };
+        self.child_reg = RegConfig::declared_on(start, self.dtb)?;
+        Ok(())
+    }
     #[inline]
     pub fn is_complete_on(&self) -> bool {
         if let ValueCursor::Body(cursor) = self.cursor {
             return cursor.is_complete_on(self.dtb);
         };
-        unreachable!("is_complete_on prop cursor");
+        false
     }
     #[inline]
     pub fn file_index_on(&self) -> usize {
-        if let ValueCursor::Body(cursor) = self.cursor {
-            return cursor.file_index_on(self.dtb);
+        match self.cursor {
+            ValueCursor::Body(cursor) => cursor.file_index_on(self.dtb),
+            ValueCursor::Prop(_, cursor) => cursor.file_index_on(self.dtb),
+            ValueCursor::Node(result) | ValueCursor::NodeIn(result) => {
+                result.start_cursor.file_index_on(self.dtb)
+            }
+        }
+    }
+    /// 取出长度恰为 `N` 字节的属性值，长度不符时返回错误。
+    pub fn prop_bytes<const N: usize>(&self, expected: &'static str) -> Result<[u8; N], DtError> {
+        match self.cursor {
+            ValueCursor::Prop(_, cursor) => cursor
+                .data_on(self.dtb)
+                .try_into()
+                .map_err(|_| self.type_mismatch(expected)),
+            _ => Err(self.type_mismatch(expected)),
+        }
+    }
+    /// 取出属性值中唯一的字符串。
+    ///
+    /// 只接受恰好一个 '\0' 结尾的字符串，字符串组应使用 `StrSeq`。
+    pub fn prop_str(&self, expected: &'static str) -> Result<&'de str, DtError> {
+        let data = match self.cursor {
+            ValueCursor::Prop(_, cursor) => cursor.data_on(self.dtb),
+            _ => return Err(self.type_mismatch(expected)),
         };
-        unreachable!("file_index_on prop cursor");
+        match data {
+            [data @ .., b'\0'] if !data.contains(&b'\0') => {
+                core::str::from_utf8(data).map_err(|e| DtError::utf8(e, self.file_index_on()))
+            }
+            _ => Err(self.type_mismatch(expected)),
+        }
+    }
+    /// 当前的值无法解析为 `expected` 类型时，构造对应的错误。
+    pub fn type_mismatch(&self, expected: &'static str) -> DtError {
+        match self.cursor {
+            ValueCursor::Prop(_, cursor) => DtError::type_mismatch(
+                expected,
+                cursor.data_on(self.dtb).len(),
+                self.file_index_on(),
+            ),
+            _ => DtError::unexpected_node(expected, self.file_index_on()),
+        }
+    }
+}
+
+/// 若 `data` 是一组非空、可打印的 '\0' 结尾字符串，返回字符串的数量，否则返回 0。
+fn printable_str_count(data: &[u8]) -> usize {
+    if !matches!(data, [b' '..=b'~', .., b'\0']) {
+        return 0;
+    }
+    let mut count = 0;
+    let mut prev = data[0];
+    for &byte in data {
+        match byte {
+            b'\0' if prev != b'\0' => count += 1,
+            b' '..=b'~' => {}
+            _ => return 0,
+        }
+        prev = byte;
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{Error, ErrorType};
+    use crate::{Dtb, DtbPtr, buildin::Node, buildin::Reg, from_raw_mut};
+    use serde::{Deserialize, Serialize};
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[repr(align(8))]
+    struct AlignedBuffer {
+        pub data: [u8; RAW_DEVICE_TREE.len()],
+    }
+    #[test]
+    fn test_type_mismatch() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        #[derive(Deserialize)]
+        #[allow(unused)]
+        struct Tree {
+            model: u32,
+        }
+        #[derive(Deserialize)]
+        #[allow(unused)]
+        struct Cpus {
+            #[serde(rename = "timebase-frequency")]
+            timebase_frequency: bool,
+        }
+        #[derive(Deserialize)]
+        #[allow(unused)]
+        struct Root {
+            cpus: u32,
+        }
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        assert!(matches!(
+            node.try_deserialize::<Tree>(),
+            Err(Error::Typed {
+                error_type: ErrorType::TypeMismatch {
+                    expected: "u32",
+                    found_len: 18
+                },
+                ..
+            })
+        ));
+        assert!(matches!(
+            node.find("/cpus").unwrap().try_deserialize::<Cpus>(),
+            Err(Error::Typed {
+                error_type: ErrorType::TypeMismatch {
+                    expected: "bool",
+                    found_len: 4
+                },
+                ..
+            })
+        ));
+        assert!(matches!(
+            node.try_deserialize::<Root>(),
+            Err(Error::Typed {
+                error_type: ErrorType::UnexpectedNode { expected: "u32" },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_borrowed_str() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        #[derive(Deserialize)]
+        struct Tree<'a> {
+            model: &'a str,
+            compatible: &'a str,
+        }
+        #[derive(Deserialize)]
+        #[allow(unused)]
+        struct Test<'a> {
+            compatible: &'a str,
+        }
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let tree: Tree = node.deserialize();
+        assert_eq!(tree.model, "riscv-virtio,qemu");
+        assert_eq!(tree.compatible, "riscv-virtio");
+        let status = node.find("/cpus/cpu@0").unwrap().get_prop("status");
+        assert_eq!(status.unwrap().deserialize::<&str>(), "okay");
+        // 这个节点的 `compatible` 包含三个字符串。
+        assert!(matches!(
+            node.find("/soc/test@100000")
+                .unwrap()
+                .try_deserialize::<Test>(),
+            Err(Error::Typed {
+                error_type: ErrorType::TypeMismatch {
+                    expected: "str",
+                    ..
+                },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_tuple_and_array() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Region(u64, u64);
+        #[derive(Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        struct Pci {
+            bus_range: (u32, u32),
+            reg: Region,
+            interrupt_map_mask: [u32; 4],
+        }
+        #[derive(Deserialize)]
+        struct Memory {
+            reg: [u32; 4],
+        }
+        #[derive(Deserialize)]
+        #[allow(unused)]
+        struct Mismatch {
+            reg: [u32; 3],
+        }
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let pci: Pci = node.find("/soc/pci@30000000").unwrap().deserialize();
+        assert_eq!(pci.bus_range, (0, 0xff));
+        assert_eq!(pci.reg, Region(0x3000_0000, 0x1000_0000));
+        assert_eq!(pci.interrupt_map_mask, [0x1800, 0, 0, 7]);
+        let memory = node.find("/memory@80000000").unwrap();
+        assert_eq!(memory.deserialize::<Memory>().reg, [0, 0x8000_0000, 1, 0]);
+        assert_eq!(
+            memory.get_prop("reg").unwrap().deserialize::<[u64; 2]>(),
+            [0x8000_0000, 0x1_0000_0000]
+        );
+        assert!(matches!(
+            memory.try_deserialize::<Mismatch>(),
+            Err(Error::Typed {
+                error_type: ErrorType::TypeMismatch {
+                    expected: "tuple",
+                    found_len: 16
+                },
+                ..
+            })
+        ));
+        assert!(
+            memory
+                .get_prop("reg")
+                .unwrap()
+                .try_deserialize::<[u64; 3]>()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_unit_enum() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Status {
+            Okay,
+            Disabled,
+        }
+        #[derive(Deserialize, Debug, PartialEq)]
+        enum MmuType {
+            #[serde(rename = "riscv,sv39")]
+            Sv39,
+            #[serde(rename = "riscv,sv57")]
+            Sv57,
+        }
+        #[derive(Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        struct Cpu {
+            status: Status,
+            mmu_type: MmuType,
+        }
+        #[derive(Deserialize)]
+        #[allow(unused)]
+        struct Model {
+            model: MmuType,
+        }
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let cpu: Cpu = node.find("/cpus/cpu@0").unwrap().deserialize();
+        assert_eq!(cpu.status, Status::Okay);
+        assert_eq!(cpu.mmu_type, MmuType::Sv57);
+        assert!(node.try_deserialize::<Model>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize_any() {
+        use std::collections::BTreeMap;
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        #[derive(Deserialize, Debug, PartialEq)]
+        #[serde(untagged)]
+        enum Value<'a> {
+            Flag(bool),
+            Cell(u32),
+            Str(&'a str),
+            StrList(Vec<&'a str>),
+            Bytes(&'a [u8]),
+            #[serde(borrow)]
+            Node(BTreeMap<&'a str, Value<'a>>),
+        }
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let test: BTreeMap<&str, Value> = node.find("/soc/test@100000").unwrap().deserialize();
+        assert_eq!(test["phandle"], Value::Cell(4));
+        assert_eq!(
+            test["compatible"],
+            Value::StrList(vec!["sifive,test1", "sifive,test0", "syscon"])
+        );
+        let memory: BTreeMap<&str, Value> = node.find("/memory@80000000").unwrap().deserialize();
+        assert!(matches!(memory["reg"], Value::Bytes(reg) if reg.len() == 16));
+
+        let cpu: BTreeMap<&str, Value> = node.find("/cpus/cpu@0").unwrap().deserialize();
+        assert_eq!(cpu["device_type"], Value::Str("cpu"));
+        let Value::Node(intc) = &cpu["interrupt-controller"] else {
+            panic!("interrupt-controller should be a node");
+        };
+        assert_eq!(intc["interrupt-controller"], Value::Flag(true));
+        assert_eq!(intc["compatible"], Value::Str("riscv,cpu-intc"));
+        assert!(!intc.contains_key("/"));
+    }
+
+    #[test]
+    fn test_buffered_builtin() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum MaybeReg<'a> {
+            #[serde(borrow)]
+            Reg(Reg<'a>),
+            Bytes(&'a [u8]),
+        }
+        #[derive(Deserialize)]
+        struct Memory<'a> {
+            #[serde(borrow)]
+            reg: MaybeReg<'a>,
+        }
+
+        // `untagged` 缓冲后的值不是设备树解析器，内置类型报错而不是错误地转换。
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let memory: Memory = node.find("/memory@80000000").unwrap().deserialize();
+        assert!(matches!(memory.reg, MaybeReg::Bytes(reg) if reg.len() == 16));
+    }
+
+    /// 以原始字节写入的属性值。
+    struct RawBytes(&'static [u8]);
+
+    impl Serialize for RawBytes {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_bytes(self.0)
+        }
+    }
+
+    #[repr(align(8))]
+    struct SerBuffer {
+        pub data: [u8; 512],
+    }
+
+    #[test]
+    fn test_integers() {
+        #[derive(Serialize)]
+        struct Raw {
+            byte: RawBytes,
+            half: RawBytes,
+            word: RawBytes,
+            double: RawBytes,
+        }
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Unsigned {
+            byte: u8,
+            half: u16,
+            word: u32,
+            double: u64,
+        }
+        #[derive(Deserialize, Debug, PartialEq)]
+        struct Signed {
+            byte: i8,
+            half: i16,
+            word: i32,
+            double: i64,
+        }
+        #[derive(Deserialize)]
+        #[allow(unused)]
+        struct Mismatch {
+            word: u64,
+        }
+        let mut buf = SerBuffer { data: [0; 512] };
+        let raw = Raw {
+            byte: RawBytes(&[0xfe]),
+            half: RawBytes(&[0xff, 0xfd]),
+            word: RawBytes(&[0xff, 0xff, 0xff, 0xfc]),
+            double: RawBytes(&[0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00]),
+        };
+        crate::ser::to_dtb(&raw, &[], &mut buf.data).unwrap();
+
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        assert_eq!(
+            node.deserialize::<Unsigned>(),
+            Unsigned {
+                byte: 0xfe,
+                half: 0xfffd,
+                word: 0xfffffffc,
+                double: 0x1_0000_0000,
+            }
+        );
+        assert_eq!(
+            node.deserialize::<Signed>(),
+            Signed {
+                byte: -2,
+                half: -3,
+                word: -4,
+                double: 0x1_0000_0000,
+            }
+        );
+        assert!(matches!(
+            node.try_deserialize::<Mismatch>(),
+            Err(Error::Typed {
+                error_type: ErrorType::TypeMismatch {
+                    expected: "u64",
+                    found_len: 4
+                },
+                ..
+            })
+        ));
     }
 }
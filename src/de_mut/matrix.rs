@@ -1,5 +1,5 @@
 use crate::de_mut::ValueCursor;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de};
 
 pub struct Matrix<'de, const T: usize> {
     data: &'de [u32],
@@ -63,14 +63,22 @@ impl<'de, const T: usize> Deserialize<'de> for Matrix<'_, T> {
         let value_deserialzer = super::ValueDeserializer::deserialize(deserializer)?;
         let data = match value_deserialzer.cursor {
             ValueCursor::Prop(_, cursor) => cursor.data_on(value_deserialzer.dtb),
-            _ => unreachable!(),
+            _ => {
+                return Err(de::Error::invalid_type(
+                    de::Unexpected::Map,
+                    &"matrix property",
+                ));
+            }
         };
         if data.len() % Self::get_block_size() != 0 {
-            panic!("unaligned matrix");
+            return Err(de::Error::invalid_length(
+                data.len(),
+                &"a multiple of matrix block size",
+            ));
         }
         let (prefix, data, suffix) = unsafe { data.align_to::<u32>() };
         if !prefix.is_empty() || !suffix.is_empty() {
-            panic!("Not support unaligned data");
+            return Err(de::Error::custom("unaligned matrix data"));
         }
 
         Ok(Self { data })
@@ -125,4 +133,24 @@ mod tests {
         }
         assert_eq!(count, 16);
     }
+    #[test]
+    fn try_deserialize_unaligned_matrix() {
+        #[derive(Serialize)]
+        struct Base {
+            pub hello: [u32; 3],
+        }
+        let mut buf1 = [0u8; MAX_SIZE];
+
+        {
+            let base = Base { hello: [1, 2, 3] };
+            crate::ser::to_dtb(&base, &[], &mut buf1).unwrap();
+        }
+
+        let ptr = DtbPtr::from_raw(buf1.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let prop = node.get_prop("hello").unwrap();
+        assert!(prop.try_deserialize::<Matrix<2>>().is_err());
+        assert_eq!(prop.try_deserialize::<Matrix<3>>().unwrap().len(), 1);
+    }
 }
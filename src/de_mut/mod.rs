@@ -4,26 +4,39 @@
 use crate::error::Error as DtError;
 use serde::de;
 
+mod cells;
 mod cursor;
 mod data;
 // mod group;
 mod matrix;
 pub(crate) mod node;
 mod node_seq;
+mod phandle_args;
+mod ranges;
 mod reg;
 mod str_seq;
 // mod r#struct;
 mod struct_access;
 mod structs;
+pub(crate) mod walk;
 
 const VALUE_DESERIALIZER_NAME: &str = "$serde_device_tree$de_mut$ValueDeserializer";
 pub(crate) const NODE_NAME: &str = "$serde_device_tree$de_mut$Node";
 pub(crate) const NODE_NODE_ITEM_NAME: &str = "$serde_device_tree$de_mut$Node$NodeItem";
 // pub(crate) const NODE_PROP_ITEM_NAME: &str = "$serde_device_tree$de_mut$Node$PropItem";
 
-pub use structs::{Dtb, DtbPtr};
+pub use structs::{Dtb, DtbPtr, MemRsvIter};
 pub mod buildin {
-    pub use super::{matrix::Matrix, node::Node, node_seq::NodeSeq, reg::Reg, str_seq::StrSeq};
+    pub use super::{
+        matrix::Matrix,
+        node::Node,
+        node_seq::NodeSeq,
+        phandle_args::PhandleArgs,
+        ranges::{DmaRanges, Ranges},
+        reg::Reg,
+        str_seq::StrSeq,
+        walk::WalkAction,
+    };
 }
 
 use cursor::{BodyCursor, Cursor, MultiNodeCursor, PropCursor};
@@ -45,6 +58,7 @@ where
     let mut d = ValueDeserializer {
         dtb,
         reg: RegConfig::DEFAULT,
+        child_reg: RegConfig::DEFAULT,
         cursor: ValueCursor::NodeIn(MultiNodeCursor {
             start_cursor: BodyCursor::STARTER,
             skip_cursor: BodyCursor::ROOT, // This item will never be used.
@@ -1,20 +1,25 @@
 use super::{
-    BodyCursor, Cursor, MultiNodeCursor, PropCursor, RefDtb, RegConfig, ValueCursor,
-    ValueDeserializer,
+    BodyCursor, Cursor, DtError, MemRsvIter, MultiNodeCursor, PropCursor, RefDtb, RegConfig,
+    ValueCursor, ValueDeserializer,
 };
+use crate::Status;
 use core::fmt::Debug;
 use core::marker::PhantomData;
 use serde::de::MapAccess;
 use serde::{Deserialize, Serialize, de};
 
-// TODO: Spec 2.3.5 said that we should not inherited from ancestors and the size-cell &
-// address-cells should only used for current node's children.
+/// 节点对象。
+///
+/// 按照规范 2.3.5 节，`#address-cells` 和 `#size-cells` 只作用于直接子节点：
+/// 节点自身的 `reg` 使用父节点声明的格式，子节点使用本节点声明的格式，
+/// 未声明时使用默认值 2 和 1。
 #[allow(unused)]
 #[derive(Clone)]
 pub struct Node<'de> {
-    dtb: RefDtb<'de>,
-    reg: RegConfig,
-    cursor: BodyCursor,
+    pub(super) dtb: RefDtb<'de>,
+    pub(super) reg: RegConfig,
+    pub(super) child_reg: RegConfig,
+    pub(super) cursor: BodyCursor,
     props_start: Option<BodyCursor>,
     nodes_start: Option<BodyCursor>,
 }
@@ -41,18 +46,39 @@ pub struct PropIter<'de, 'b> {
     i: usize,
 }
 
+/// 节点的内容。
+///
+/// 节点序列化为包装这个类型的新类型结构体，使设备树序列化器能识别出节点。
+pub(crate) struct NodeContent<'a, 'de>(pub &'a Node<'de>);
+
+/// 只解析节点的 `status` 属性。
+#[derive(Deserialize)]
+pub(super) struct StatusProp<'a> {
+    #[serde(borrow)]
+    status: Option<Status<'a>>,
+}
+
 /// 属性对象。
 #[allow(unused)]
 pub struct PropItem<'de> {
     dtb: RefDtb<'de>,
     reg: RegConfig,
+    child_reg: RegConfig,
     body: BodyCursor,
     prop: PropCursor,
     name: &'de str,
 }
 
 impl<'de> Node<'de> {
+    /// 反序列化节点的内容。
+    ///
+    /// 解析失败时 panic，需要处理错误时使用 [`Node::try_deserialize`]。
     pub fn deserialize<T: Deserialize<'de>>(&self) -> T {
+        self.try_deserialize().unwrap()
+    }
+
+    /// 尝试反序列化节点的内容，解析失败时返回错误。
+    pub fn try_deserialize<T: Deserialize<'de>>(&self) -> Result<T, DtError> {
         use super::ValueCursor;
         let result = match self.cursor.clone().move_on(self.dtb) {
             Cursor::Title(c) => {
@@ -65,9 +91,9 @@ impl<'de> Node<'de> {
         T::deserialize(&mut ValueDeserializer {
             dtb: self.dtb,
             reg: self.reg,
+            child_reg: self.child_reg,
             cursor: ValueCursor::NodeIn(result),
         })
-        .unwrap()
     }
     // TODO: Maybe use BTreeMap when have alloc
     /// 获得节点迭代器。
@@ -79,6 +105,21 @@ impl<'de> Node<'de> {
         }
     }
 
+    /// 获得从子节点 `item` 之后开始的节点迭代器，用于在不借用本节点时恢复迭代。
+    pub(crate) fn nodes_after<'b>(&'b self, item: &NodeItem<'de>) -> NodeIter<'de, 'b> {
+        NodeIter {
+            node: self,
+            cursor: Some(item.node.skip_cursor),
+            i: 0,
+        }
+    }
+
+    /// 获得可用节点的迭代器，跳过 `status` 不为 `okay` 的子节点。
+    pub fn enabled_nodes<'b>(&'b self) -> impl Iterator<Item = NodeItem<'de>> + 'b {
+        self.nodes()
+            .filter(|node| StatusProp::is_okay(node.try_deserialize()))
+    }
+
     /// 获得属性迭代器。
     pub fn props<'b>(&'b self) -> PropIter<'de, 'b> {
         PropIter {
@@ -89,7 +130,7 @@ impl<'de> Node<'de> {
     }
 
     /// 尝试获得指定属性
-    pub fn get_prop<'b>(&'b self, name: &str) -> Option<PropItem<'b>> {
+    pub fn get_prop(&self, name: &str) -> Option<PropItem<'de>> {
         self.props().find(|prop| prop.get_name() == name)
     }
 
@@ -102,6 +143,65 @@ impl<'de> Node<'de> {
             todo!();
         }
     }
+
+    /// 获得节点所在设备树的根节点。
+    pub(crate) fn root(&self) -> Result<Node<'de>, DtError> {
+        super::from_raw_mut(self.dtb)
+    }
+
+    /// 如果两个对象指向设备树中的同一个节点，返回 `true`。
+    pub(crate) fn is_same(&self, other: &Node) -> bool {
+        self.cursor == other.cursor
+    }
+
+    /// 获得节点所在设备树的内存保留块迭代器。
+    pub(crate) fn memory_reservations(&self) -> MemRsvIter<'de> {
+        MemRsvIter::on(self.dtb)
+    }
+
+    /// 从节点开始处的光标直接构造节点对象，不扫描子树。
+    ///
+    /// `reg` 是父节点为子节点声明的地址空间格式。
+    pub(super) fn new_on(
+        dtb: RefDtb<'de>,
+        reg: RegConfig,
+        start: BodyCursor,
+    ) -> Result<Self, DtError> {
+        let mut title = start;
+        let body = match title.move_on(dtb) {
+            Cursor::Title(c) => c.split_on(dtb).1,
+            _ => unreachable!("Node's cursor should on its start"),
+        };
+        let child_reg = RegConfig::declared_on(body, dtb)?;
+        let mut props_start = None;
+        let mut cursor = body;
+        let nodes_start = loop {
+            let origin = cursor;
+            match cursor.move_on(dtb) {
+                Cursor::Prop(c) => {
+                    props_start.get_or_insert(origin);
+                    cursor = c.name_on(dtb).1;
+                }
+                Cursor::Title(_) => break Some(cursor),
+                Cursor::End => break None,
+            }
+        };
+        Ok(Node {
+            dtb,
+            reg,
+            child_reg,
+            cursor: title,
+            props_start,
+            nodes_start,
+        })
+    }
+}
+
+impl StatusProp<'_> {
+    /// 如果节点可用，即没有 `status` 属性或其值为 `okay`，返回 `true`。
+    pub fn is_okay(result: Result<Self, DtError>) -> bool {
+        result.is_ok_and(|prop| prop.status.is_none_or(|status| status.is_okay()))
+    }
 }
 
 impl Debug for Node<'_> {
@@ -148,7 +248,7 @@ impl<'de> Iterator for NodeIter<'de, '_> {
                 let node_cursor = c.take_node_on(dtb, name);
                 let res = Some(Self::Item {
                     dtb,
-                    reg: self.node.reg,
+                    reg: self.node.child_reg,
                     node: node_cursor,
                     name,
                 });
@@ -176,6 +276,7 @@ impl<'de> Iterator for PropIter<'de, '_> {
                     dtb,
                     body: *cursor,
                     reg: self.node.reg,
+                    child_reg: self.node.child_reg,
                     prop: c,
                     name,
                 });
@@ -213,13 +314,15 @@ impl<'de> Deserialize<'de> for Node<'_> {
                 // into our map.
                 let mut dtb: Option<RefDtb<'b>> = None;
                 let mut reg: Option<RegConfig> = None;
+                let mut child_reg: Option<RegConfig> = None;
                 let mut props_start: Option<BodyCursor> = None;
                 let mut nodes_start: Option<BodyCursor> = None;
                 let mut self_cursor: Option<BodyCursor> = None;
                 while let Some((key, value)) = access.next_entry::<&str, ValueDeserializer<'b>>()? {
                     dtb = Some(value.dtb);
-                    reg = Some(value.reg);
                     if key == "/" {
+                        reg = Some(value.reg);
+                        child_reg = Some(value.child_reg);
                         self_cursor = match value.cursor {
                             ValueCursor::NodeIn(result) => Some(result.start_cursor),
                             _ => {
@@ -246,6 +349,7 @@ impl<'de> Deserialize<'de> for Node<'_> {
                 Ok(Node {
                     dtb: dtb.unwrap(),
                     reg: reg.unwrap(),
+                    child_reg: child_reg.unwrap(),
                     cursor: self_cursor.unwrap(),
                     nodes_start,
                     props_start,
@@ -265,13 +369,20 @@ impl<'de> Deserialize<'de> for Node<'_> {
 
 impl<'de> NodeItem<'de> {
     /// 反序列化一个节点的内容。
+    ///
+    /// 解析失败时 panic，需要处理错误时使用 [`NodeItem::try_deserialize`]。
     pub fn deserialize<T: Deserialize<'de>>(&self) -> T {
+        self.try_deserialize().unwrap()
+    }
+
+    /// 尝试反序列化一个节点的内容，解析失败时返回错误。
+    pub fn try_deserialize<T: Deserialize<'de>>(&self) -> Result<T, DtError> {
         T::deserialize(&mut ValueDeserializer {
             dtb: self.dtb,
             reg: self.reg,
+            child_reg: RegConfig::DEFAULT,
             cursor: ValueCursor::NodeIn(self.node),
         })
-        .unwrap()
     }
 
     pub fn get_parsed_name(&self) -> (&str, Option<&str>) {
@@ -300,14 +411,35 @@ impl<'de> PropItem<'de> {
     pub fn get_name(&self) -> &'de str {
         self.name
     }
+    /// 以 32 位 cell 数组的形式获得属性的值，cell 保持大端序。
+    pub(crate) fn cells(&self) -> Option<&'de [u32]> {
+        let (prefix, data, suffix) = unsafe { self.prop.data_on(self.dtb).align_to::<u32>() };
+        (prefix.is_empty() && suffix.is_empty()).then_some(data)
+    }
+    /// 以一个或两个 cell 组成的整数形式获得属性的值，如频率、大小等。
+    pub(crate) fn cells_u64(&self) -> Option<u64> {
+        match *self.cells()? {
+            [value] => Some(u32::from_be(value) as u64),
+            [high, low] => Some((u32::from_be(high) as u64) << 32 | u32::from_be(low) as u64),
+            _ => None,
+        }
+    }
+    /// 反序列化属性的值。
+    ///
+    /// 解析失败时 panic，需要处理错误时使用 [`PropItem::try_deserialize`]。
     pub fn deserialize<T: Deserialize<'de>>(&self) -> T {
+        self.try_deserialize().unwrap()
+    }
+
+    /// 尝试反序列化属性的值，解析失败时返回错误。
+    pub fn try_deserialize<T: Deserialize<'de>>(&self) -> Result<T, DtError> {
         use super::ValueCursor;
         T::deserialize(&mut ValueDeserializer {
             dtb: self.dtb,
             reg: self.reg,
+            child_reg: self.child_reg,
             cursor: ValueCursor::Prop(self.body, self.prop),
         })
-        .unwrap()
     }
 }
 impl Serialize for NodeItem<'_> {
@@ -329,16 +461,25 @@ impl Serialize for PropItem<'_> {
 }
 
 impl Serialize for Node<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_newtype_struct(crate::de_mut::NODE_NAME, &NodeContent(self))
+    }
+}
+
+impl Serialize for NodeContent<'_, '_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
         use serde::ser::SerializeMap;
         let mut map = serializer.serialize_map(None)?;
-        for prop in self.props() {
+        for prop in self.0.props() {
             map.serialize_entry(prop.get_name(), &prop)?;
         }
-        for node in self.nodes() {
+        for node in self.0.nodes() {
             map.serialize_entry(node.get_full_name(), &node.deserialize::<Node>())?;
         }
         map.end()
@@ -347,7 +488,9 @@ impl Serialize for Node<'_> {
 
 #[cfg(test)]
 mod tests {
+    use crate::buildin::Reg;
     use crate::{Dtb, DtbPtr, buildin::Node, from_raw_mut};
+    use serde::{Deserialize, Serialize};
     const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/hifive-unmatched-a00.dtb");
     const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
     #[repr(align(8))]
@@ -368,4 +511,88 @@ mod tests {
         let prop = node.get_prop("compatible");
         assert!(prop.is_some());
     }
+
+    #[test]
+    fn test_reg_uses_parent_cells() {
+        #[derive(Serialize)]
+        struct Root {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            bus: Bus,
+        }
+        #[derive(Serialize)]
+        struct Bus {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            reg: (u32, u32),
+            dev: Dev,
+        }
+        #[derive(Serialize)]
+        struct Dev {
+            reg: (u32, u32, u32),
+        }
+        #[derive(Deserialize)]
+        struct Tree<'a> {
+            #[serde(borrow)]
+            bus: BusReg<'a>,
+        }
+        #[derive(Deserialize)]
+        struct BusReg<'a> {
+            #[serde(borrow)]
+            reg: Reg<'a>,
+            dev: DevReg<'a>,
+        }
+        #[derive(Deserialize)]
+        struct DevReg<'a> {
+            #[serde(borrow)]
+            reg: Reg<'a>,
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 512],
+        }
+        let mut buf = SerBuffer { data: [0; 512] };
+        let root = Root {
+            address_cells: 1,
+            size_cells: 1,
+            bus: Bus {
+                address_cells: 2,
+                size_cells: 1,
+                reg: (0x1000, 0x100),
+                dev: Dev {
+                    reg: (0x1, 0x2000, 0x10),
+                },
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        // `bus` 的 `reg` 按根节点声明的 1、1 解析，`dev` 的 `reg` 按 `bus` 声明的 2、1 解析。
+        let tree: Tree = from_raw_mut(&dtb).unwrap();
+        assert_eq!(tree.bus.reg.iter().next().unwrap().0, 0x1000..0x1100);
+        assert_eq!(
+            tree.bus.dev.reg.iter().next().unwrap().0,
+            0x1_0000_2000..0x1_0000_2010
+        );
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let reg = |path: &str| {
+            node.find(path)
+                .unwrap()
+                .get_prop("reg")
+                .unwrap()
+                .deserialize::<Reg>()
+                .iter()
+                .next()
+                .unwrap()
+                .0
+        };
+        assert_eq!(reg("/bus"), 0x1000..0x1100);
+        assert_eq!(reg("/bus/dev"), 0x1_0000_2000..0x1_0000_2010);
+    }
 }
@@ -1,4 +1,5 @@
-use super::{BodyCursor, Cursor, RefDtb, RegConfig, ValueCursor, ValueDeserializer};
+use super::node::StatusProp;
+use super::{BodyCursor, Cursor, DtError, RefDtb, RegConfig, ValueCursor, ValueDeserializer};
 use core::{fmt::Debug, marker::PhantomData};
 use serde::de::SeqAccess;
 use serde::{Deserialize, de};
@@ -104,6 +105,12 @@ impl<'de> NodeSeq<'de> {
             i: 0,
         }
     }
+
+    /// 获得可用节点的迭代器，跳过 `status` 不为 `okay` 的节点。
+    pub fn iter_enabled<'b>(&'b self) -> impl Iterator<Item = NodeSeqItem<'de>> + 'b {
+        self.iter()
+            .filter(|node| StatusProp::is_okay(node.try_deserialize()))
+    }
 }
 
 impl Debug for NodeSeq<'_> {
@@ -164,19 +171,27 @@ impl NodeSeqItem<'_> {
 
 impl<'de> NodeSeqItem<'de> {
     /// 反序列化一个节点的内容。
+    ///
+    /// 解析失败时 panic，需要处理错误时使用 [`NodeSeqItem::try_deserialize`]。
     pub fn deserialize<T: Deserialize<'de>>(&self) -> T {
+        self.try_deserialize().unwrap()
+    }
+
+    /// 尝试反序列化一个节点的内容，解析失败时返回错误。
+    pub fn try_deserialize<T: Deserialize<'de>>(&self) -> Result<T, DtError> {
         T::deserialize(&mut ValueDeserializer {
             dtb: self.dtb,
             reg: self.reg,
+            child_reg: RegConfig::DEFAULT,
             cursor: ValueCursor::Body(self.body),
         })
-        .unwrap()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::buildin::{NodeSeq, Reg};
+    use crate::buildin::{Node, NodeSeq, Reg};
+    use crate::error::{Error, ErrorType};
     use crate::{Dtb, DtbPtr, from_raw_mut};
     use serde::Deserialize;
 
@@ -253,4 +268,35 @@ mod tests {
             .0;
         assert_eq!(range, 2147483648..6442450944);
     }
+    #[test]
+    fn test_nodeseq_item_mismatch() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE_QEMU.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE_QEMU],
+        });
+        aligned_data.data[..BUFFER_SIZE_QEMU].clone_from_slice(RAW_DEVICE_TREE_QEMU);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let t: Tree = from_raw_mut(&dtb).unwrap();
+        let item = t.memory.iter().next().unwrap();
+        assert!(matches!(
+            item.try_deserialize::<Vec<u32>>(),
+            Err(Error::Typed {
+                error_type: ErrorType::UnexpectedNode { .. },
+                ..
+            })
+        ));
+        assert!(matches!(
+            item.try_deserialize::<Node>(),
+            Err(Error::Typed {
+                error_type: ErrorType::UnexpectedNode { .. },
+                ..
+            })
+        ));
+    }
 }
@@ -0,0 +1,324 @@
+use super::{DtError, RefDtb, ValueCursor, node::Node};
+use crate::utils::phandle::PhandleIndex;
+use serde::{Deserialize, de};
+
+/// 一组带参数的 phandle 引用，如 `clocks = <&clk 1>, <&osc>;`。
+///
+/// 每项由提供者的 phandle 和若干个参数组成，
+/// 参数的数量由提供者的 `#<名字>-cells` 属性决定，因此各项的长度可以不同。
+/// 名字由属性名推导：`clocks` 对应 `#clock-cells`，`mboxes` 对应 `#mbox-cells`，
+/// `gpios` 和 `*-gpios` 对应 `#gpio-cells`，`interrupts-extended` 对应 `#interrupt-cells`。
+pub struct PhandleArgs<'de> {
+    dtb: RefDtb<'de>,
+    name: &'de str,
+    data: &'de [u32],
+    file_index: usize,
+}
+
+/// 一项带参数的 phandle 引用。
+///
+/// 与 [`Matrix`](super::buildin::Matrix) 相同，参数保持设备树中的大端序，使用前需要 `u32::from_be` 转换。
+pub struct PhandleArg<'de> {
+    /// 提供者节点。
+    pub provider: Node<'de>,
+    /// 引用的参数。
+    pub args: &'de [u32],
+}
+
+/// 带参数的 phandle 引用迭代器。
+///
+/// 找不到提供者、提供者没有参数数量属性或属性长度不足时，产生错误，之后迭代结束。
+pub struct PhandleArgsIter<'a, 'de> {
+    root: Result<Node<'de>, DtError>,
+    index: Option<&'a PhandleIndex<'a, 'de>>,
+    cells: &'de str,
+    data: &'de [u32],
+    file_index: usize,
+}
+
+impl<'de> Deserialize<'de> for PhandleArgs<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value_deserialzer = super::ValueDeserializer::deserialize(deserializer)?;
+        let dtb = value_deserialzer.dtb;
+        let (name, data, file_index) = match value_deserialzer.cursor {
+            ValueCursor::Prop(_, cursor) => (
+                cursor.name_on(dtb).0,
+                cursor.data_on(dtb),
+                cursor.file_index_on(dtb),
+            ),
+            _ => {
+                return Err(de::Error::invalid_type(
+                    de::Unexpected::Map,
+                    &"phandle list property",
+                ));
+            }
+        };
+        let (prefix, data, suffix) = unsafe { data.align_to::<u32>() };
+        if !prefix.is_empty() || !suffix.is_empty() {
+            return Err(de::Error::invalid_length(
+                prefix.len() + data.len() * 4 + suffix.len(),
+                &"a multiple of 4 bytes",
+            ));
+        }
+
+        Ok(Self {
+            dtb,
+            name,
+            data,
+            file_index,
+        })
+    }
+}
+
+impl<'de> PhandleArgs<'de> {
+    /// 获得引用迭代器，按属性名推导提供者的参数数量属性。
+    pub fn iter(&self) -> PhandleArgsIter<'_, 'de> {
+        let cells = match self.name {
+            "mboxes" => "mbox",
+            "interrupts-extended" => "interrupt",
+            name if name == "gpios" || name.ends_with("-gpios") => "gpio",
+            name => name.strip_suffix('s').unwrap_or(name),
+        };
+        self.iter_with(cells)
+    }
+
+    /// 获得引用迭代器，提供者的参数数量由 `#<cells>-cells` 属性给出。
+    pub fn iter_with(&self, cells: &'de str) -> PhandleArgsIter<'_, 'de> {
+        PhandleArgsIter {
+            root: super::from_raw_mut(self.dtb),
+            index: None,
+            cells,
+            data: self.data,
+            file_index: self.file_index,
+        }
+    }
+}
+
+impl<'a, 'de> PhandleArgsIter<'a, 'de> {
+    /// 使用预先建立的 phandle 索引查找提供者，避免每一项都扫描整个结构块。
+    pub fn with_index(self, index: &'a PhandleIndex<'a, 'de>) -> Self {
+        Self {
+            index: Some(index),
+            ..self
+        }
+    }
+
+    fn provider(&self, phandle: u32) -> Result<Node<'de>, DtError> {
+        let provider = match self.index {
+            Some(index) => index.get(phandle).cloned(),
+            None => self.root.clone()?.find_by_phandle(phandle),
+        };
+        provider.ok_or(DtError::phandle_not_found(phandle, self.file_index))
+    }
+
+    fn next_arg(&mut self, phandle: u32, rest: &'de [u32]) -> Result<PhandleArg<'de>, DtError> {
+        let provider = self.provider(phandle)?;
+        let count = provider
+            .props()
+            .find(|prop| {
+                let name = prop.get_name();
+                name.strip_prefix('#')
+                    .and_then(|s| s.strip_suffix("-cells"))
+                    == Some(self.cells)
+            })
+            .and_then(|prop| prop.try_deserialize::<u32>().ok())
+            .ok_or(DtError::buildin_type_parsed_error(
+                "#<name>-cells of provider",
+                self.file_index,
+            ))? as usize;
+        if rest.len() < count {
+            return Err(DtError::slice_eof_unpexpected(
+                count as u32,
+                rest.len() as u32,
+                self.file_index,
+            ));
+        }
+        let (args, rest) = rest.split_at(count);
+        self.data = rest;
+        Ok(PhandleArg { provider, args })
+    }
+}
+
+impl<'de> Iterator for PhandleArgsIter<'_, 'de> {
+    type Item = Result<PhandleArg<'de>, DtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (phandle, rest) = self.data.split_first()?;
+            let phandle = u32::from_be(*phandle);
+            // phandle 为 0 的项是占位符，没有参数
+            if phandle == 0 {
+                self.data = rest;
+                continue;
+            }
+            let result = self.next_arg(phandle, rest);
+            if result.is_err() {
+                self.data = &[];
+            }
+            return Some(result);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PhandleArgs;
+    use crate::{
+        Dtb, DtbPtr,
+        buildin::Node,
+        error::{Error, ErrorType},
+        from_raw_mut,
+    };
+    use serde::Serialize;
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[repr(align(8))]
+    struct AlignedBuffer {
+        pub data: [u8; RAW_DEVICE_TREE.len()],
+    }
+    #[test]
+    fn test_interrupts_extended() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let clint = node.find("/soc/clint@2000000").unwrap();
+        let list = clint
+            .get_prop("interrupts-extended")
+            .unwrap()
+            .deserialize::<PhandleArgs>();
+        let mut iter = list.iter();
+        let arg = iter.next().unwrap().unwrap();
+        assert_eq!(arg.provider.name(), "interrupt-controller");
+        assert_eq!(arg.args, [3u32.to_be()]);
+        let arg = iter.next().unwrap().unwrap();
+        assert_eq!(arg.args, [7u32.to_be()]);
+        assert!(iter.next().is_none());
+
+        // 使用 phandle 索引查找提供者，结果相同。
+        let mut buf = [const { None }; 8];
+        let index = node.phandle_index(&mut buf).unwrap();
+        let mut iter = list.iter().with_index(&index);
+        let arg = iter.next().unwrap().unwrap();
+        assert_eq!(arg.provider.name(), "interrupt-controller");
+        assert_eq!(arg.args, [3u32.to_be()]);
+    }
+
+    #[test]
+    fn test_variable_length() {
+        #[derive(Serialize)]
+        struct Root {
+            osc: Provider<()>,
+            pll: Provider<u32>,
+            dev: Dev,
+            truncated: Broken,
+            missing: Broken,
+        }
+        #[derive(Serialize)]
+        struct Provider<T> {
+            phandle: u32,
+            #[serde(rename = "#clock-cells")]
+            clock_cells: u32,
+            #[serde(rename = "#reset-cells")]
+            reset_cells: T,
+        }
+        #[derive(Serialize)]
+        struct Dev {
+            clocks: (u32, u32, u32, u32, u32),
+        }
+        #[derive(Serialize)]
+        struct Broken {
+            clocks: (u32, u32, u32),
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 512],
+        }
+        let mut buf = SerBuffer { data: [0; 512] };
+        let root = Root {
+            osc: Provider {
+                phandle: 1,
+                clock_cells: 0,
+                reset_cells: (),
+            },
+            pll: Provider {
+                phandle: 2,
+                clock_cells: 2,
+                reset_cells: 1,
+            },
+            dev: Dev {
+                clocks: (1, 2, 5, 6, 1),
+            },
+            truncated: Broken { clocks: (1, 2, 5) },
+            missing: Broken { clocks: (9, 1, 1) },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        let clocks = node
+            .find("/dev")
+            .unwrap()
+            .get_prop("clocks")
+            .unwrap()
+            .deserialize::<PhandleArgs>();
+        let entries: Vec<_> = clocks
+            .iter()
+            .map(|arg| {
+                let arg = arg.unwrap();
+                let args: Vec<_> = arg.args.iter().map(|x| u32::from_be(*x)).collect();
+                (arg.provider.name(), args)
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            [("osc", vec![]), ("pll", vec![5, 6]), ("osc", vec![])]
+        );
+        // 按 `#reset-cells` 解析时，`osc` 的该属性为空，无法得到参数数量。
+        assert!(clocks.iter_with("reset").next().unwrap().is_err());
+
+        let clocks = |path: &str| {
+            node.find(path)
+                .unwrap()
+                .get_prop("clocks")
+                .unwrap()
+                .deserialize::<PhandleArgs>()
+        };
+        // `pll` 需要两个参数，属性中只剩一个。
+        let truncated = clocks("/truncated");
+        let mut iter = truncated.iter();
+        assert_eq!(iter.next().unwrap().unwrap().provider.name(), "osc");
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::Typed {
+                error_type: ErrorType::SliceEofUnexpected {
+                    expected_length: 2,
+                    remaining_length: 1,
+                },
+                ..
+            }))
+        ));
+        assert!(iter.next().is_none());
+        // 找不到 phandle 为 9 的提供者。
+        let missing = clocks("/missing");
+        let mut iter = missing.iter();
+        assert!(matches!(
+            iter.next(),
+            Some(Err(Error::Typed {
+                error_type: ErrorType::PhandleNotFound { phandle: 9 },
+                ..
+            }))
+        ));
+        assert!(iter.next().is_none());
+    }
+}
@@ -0,0 +1,341 @@
+use super::reg::read_cells;
+use super::{BLOCK_LEN, DtError, PropCursor, RefDtb, RegConfig, ValueCursor};
+use core::{fmt::Debug, ops::Range};
+use serde::{Deserialize, Serialize, de};
+
+/// 总线地址映射。
+///
+/// 每一项由子地址、父地址和长度组成：
+/// 子地址和长度使用本节点声明的格式，父地址使用父节点声明的格式。
+/// 空的 `ranges` 表示子地址空间与父地址空间相同。
+pub struct Ranges<'de> {
+    dtb: RefDtb<'de>,
+    cursor: PropCursor,
+    parent: RegConfig,
+    child: RegConfig,
+}
+
+/// 总线 DMA 地址映射，即 `dma-ranges` 属性。
+///
+/// 格式与 [`Ranges`] 相同，子地址是总线上的设备发起 DMA 时使用的地址，
+/// 父地址是对应的父总线地址。
+pub struct DmaRanges<'de>(Ranges<'de>);
+
+/// 地址映射迭代器。
+///
+/// 无法以 64 位表示的项（例如 PCI 总线带有标志位的子地址）产生错误，之后迭代结束。
+pub struct RangesIter<'de> {
+    data: &'de [u8],
+    file_index: usize,
+    parent: RegConfig,
+    child: RegConfig,
+}
+
+/// 一项地址映射。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RangesEntry {
+    pub child_address: u64,
+    pub parent_address: u64,
+    pub size: u64,
+}
+
+impl<'de> Deserialize<'de> for Ranges<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value_deserialzer = super::ValueDeserializer::deserialize(deserializer)?;
+
+        let ranges = Ranges {
+            dtb: value_deserialzer.dtb,
+            parent: value_deserialzer.reg,
+            child: value_deserialzer.child_reg,
+            cursor: match value_deserialzer.cursor {
+                ValueCursor::Prop(_, cursor) => cursor,
+                _ => {
+                    return Err(de::Error::invalid_type(
+                        de::Unexpected::Map,
+                        &"ranges property",
+                    ));
+                }
+            },
+        };
+
+        let data = ranges.cursor.data_on(ranges.dtb);
+        let Some(len) = ranges.entry_len() else {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Other("too many cells"),
+                &"#address-cells and #size-cells that fit in memory",
+            ));
+        };
+        let whole = match data.len().checked_rem(len) {
+            Some(rem) => rem == 0,
+            None => data.is_empty(),
+        };
+        if !whole {
+            return Err(de::Error::invalid_length(
+                data.len(),
+                &"whole ranges entries",
+            ));
+        }
+
+        Ok(ranges)
+    }
+}
+
+impl Ranges<'_> {
+    /// 一项地址映射占用的字节数，超过 `usize` 的表示范围时返回 `None`。
+    #[inline]
+    fn entry_len(&self) -> Option<usize> {
+        self.child
+            .address_cells
+            .checked_add(self.parent.address_cells)?
+            .checked_add(self.child.size_cells)?
+            .checked_mul(BLOCK_LEN)
+    }
+
+    /// 如果子地址空间与父地址空间相同，返回 `true`。
+    pub fn is_identity(&self) -> bool {
+        self.cursor.data_on(self.dtb).is_empty()
+    }
+
+    /// 获得地址映射迭代器。
+    ///
+    /// 无法以 64 位表示的项（例如 PCI 总线带有标志位的子地址）产生错误，之后迭代结束。
+    pub fn iter(&self) -> RangesIter<'_> {
+        RangesIter {
+            data: self.cursor.data_on(self.dtb),
+            file_index: self.cursor.file_index_on(self.dtb),
+            parent: self.parent,
+            child: self.child,
+        }
+    }
+
+    /// 将子地址空间中的一段地址转换到父地址空间。
+    ///
+    /// 地址段必须完整地落在某一项映射中，否则返回 `None`；
+    /// 结束地址小于起始地址时同样返回 `None`。
+    /// 在找到映射前遇到无法以 64 位表示的项时，同样返回 `None`。
+    pub fn translate(&self, region: Range<u64>) -> Option<Range<u64>> {
+        if self.is_identity() {
+            return (region.start <= region.end).then_some(region);
+        }
+        self.find_entry(|entry| {
+            let offset = region.start.checked_sub(entry.child_address)?;
+            if region.end.checked_sub(entry.child_address)? > entry.size {
+                return None;
+            }
+            let start = entry.parent_address.checked_add(offset)?;
+            let end = start.checked_add(region.end.checked_sub(region.start)?)?;
+            Some(start..end)
+        })
+    }
+
+    /// 将父地址空间中的一段地址转换到子地址空间，即 [`Ranges::translate`] 的逆变换。
+    ///
+    /// 地址段必须完整地落在某一项映射中，否则返回 `None`；
+    /// 结束地址小于起始地址时同样返回 `None`。
+    /// 在找到映射前遇到无法以 64 位表示的项时，同样返回 `None`。
+    pub fn reverse_translate(&self, region: Range<u64>) -> Option<Range<u64>> {
+        if self.is_identity() {
+            return (region.start <= region.end).then_some(region);
+        }
+        self.find_entry(|entry| {
+            let offset = region.start.checked_sub(entry.parent_address)?;
+            if region.end.checked_sub(entry.parent_address)? > entry.size {
+                return None;
+            }
+            let start = entry.child_address.checked_add(offset)?;
+            let end = start.checked_add(region.end.checked_sub(region.start)?)?;
+            Some(start..end)
+        })
+    }
+
+    /// 对每一项映射调用 `f`，返回第一个非 `None` 的结果，遇到错误时返回 `None`。
+    fn find_entry<T>(&self, mut f: impl FnMut(RangesEntry) -> Option<T>) -> Option<T> {
+        for entry in self.iter() {
+            if let Some(result) = f(entry.ok()?) {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+impl Iterator for RangesIter<'_> {
+    type Item = Result<RangesEntry, DtError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 构造 `Ranges` 时已检查过映射项长度
+        let child_len = BLOCK_LEN * self.child.address_cells;
+        let parent_len = BLOCK_LEN * self.parent.address_cells;
+        let len = child_len + parent_len + BLOCK_LEN * self.child.size_cells;
+        if len == 0 || self.data.len() < len {
+            return None;
+        }
+        let (current_block, data) = self.data.split_at(len);
+        let (child, rest) = current_block.split_at(child_len);
+        let (parent, size) = rest.split_at(parent_len);
+        match (read_cells(child), read_cells(parent), read_cells(size)) {
+            (Some(child_address), Some(parent_address), Some(size)) => {
+                self.data = data;
+                Some(Ok(RangesEntry {
+                    child_address,
+                    parent_address,
+                    size,
+                }))
+            }
+            _ => {
+                self.data = &[];
+                Some(Err(DtError::address_overflow(self.file_index)))
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DmaRanges<'_> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ranges::deserialize(deserializer).map(Self)
+    }
+}
+
+impl DmaRanges<'_> {
+    /// 如果设备发起 DMA 时使用的地址与父总线地址相同，返回 `true`。
+    pub fn is_identity(&self) -> bool {
+        self.0.is_identity()
+    }
+
+    /// 获得地址映射迭代器，子地址是设备发起 DMA 时使用的地址。
+    ///
+    /// 无法以 64 位表示的项产生错误，之后迭代结束。
+    pub fn iter(&self) -> RangesIter<'_> {
+        self.0.iter()
+    }
+
+    /// 将设备发起 DMA 时使用的一段地址转换到父总线地址空间。
+    ///
+    /// 地址段必须完整地落在某一项映射中，否则返回 `None`；
+    /// 结束地址小于起始地址时同样返回 `None`。
+    pub fn translate(&self, region: Range<u64>) -> Option<Range<u64>> {
+        self.0.translate(region)
+    }
+
+    /// 将父总线地址空间中的一段地址转换为设备发起 DMA 时使用的地址，
+    /// 即 [`DmaRanges::translate`] 的逆变换。
+    ///
+    /// 地址段必须完整地落在某一项映射中，否则返回 `None`；
+    /// 结束地址小于起始地址时同样返回 `None`。
+    pub fn reverse_translate(&self, region: Range<u64>) -> Option<Range<u64>> {
+        self.0.reverse_translate(region)
+    }
+}
+
+impl Debug for Ranges<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Serialize for Ranges<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // Pass bytes directly for Ranges.
+        serializer.serialize_bytes(self.cursor.data_on(self.dtb))
+    }
+}
+
+impl Debug for DmaRanges<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Serialize for DmaRanges<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ranges, RangesEntry};
+    use crate::{Dtb, DtbPtr, buildin::Node, from_raw_mut};
+    use serde::Deserialize;
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[repr(align(8))]
+    struct AlignedBuffer {
+        pub data: [u8; RAW_DEVICE_TREE.len()],
+    }
+    #[test]
+    fn test_ranges() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        #[derive(Deserialize)]
+        struct Bus<'a> {
+            #[serde(borrow)]
+            ranges: Ranges<'a>,
+        }
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        // `ranges` 出现在 `#address-cells` 之前，仍按本节点声明的格式解析。
+        let platform: Bus = node.find("/platform-bus@4000000").unwrap().deserialize();
+        let mut iter = platform.ranges.iter();
+        assert_eq!(
+            iter.next().unwrap().unwrap(),
+            RangesEntry {
+                child_address: 0,
+                parent_address: 0x400_0000,
+                size: 0x200_0000,
+            }
+        );
+        assert!(iter.next().is_none());
+        assert_eq!(
+            platform.ranges.translate(0x1000..0x2000),
+            Some(0x400_1000..0x400_2000)
+        );
+        assert_eq!(platform.ranges.translate(0x1ff_f000..0x200_1000), None);
+        assert_eq!(
+            platform.ranges.reverse_translate(0x400_1000..0x400_2000),
+            Some(0x1000..0x2000)
+        );
+        assert_eq!(platform.ranges.reverse_translate(0x1000..0x2000), None);
+        // 结束地址小于起始地址的地址段无法转换。
+        #[allow(clippy::reversed_empty_ranges)]
+        {
+            assert_eq!(platform.ranges.translate(0x2000..0x1000), None);
+            assert_eq!(
+                platform.ranges.reverse_translate(0x400_2000..0x400_1000),
+                None
+            );
+        }
+
+        let soc = node.find("/soc").unwrap();
+        let ranges = soc.get_prop("ranges").unwrap().deserialize::<Ranges>();
+        assert!(ranges.is_identity());
+        assert_eq!(ranges.translate(0x1000..0x2000), Some(0x1000..0x2000));
+
+        // PCI 子地址带有标志位，无法以 64 位表示的项产生错误，之后迭代结束。
+        let pci = node.find("/soc/pci@30000000").unwrap();
+        let ranges = pci.get_prop("ranges").unwrap().deserialize::<Ranges>();
+        let mut iter = ranges.iter();
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+        assert_eq!(ranges.translate(0x1000..0x2000), None);
+    }
+}
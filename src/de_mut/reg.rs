@@ -1,6 +1,6 @@
-use super::{BLOCK_LEN, PropCursor, RefDtb, ValueCursor};
+use super::{BLOCK_LEN, BodyCursor, Cursor, DtError, PropCursor, RefDtb, ValueCursor};
 use core::{fmt::Debug, ops::Range};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de};
 
 /// 节点地址空间。
 pub struct Reg<'de>(Inner<'de>);
@@ -17,10 +17,17 @@ pub struct RegIter<'de> {
     config: RegConfig,
 }
 
+/// 一个地址段。
+///
+/// 无论目标平台的指针宽度如何，地址都以 64 位表示，
+/// 32 位平台也能正确读到 4 GiB 以上的内存。
+/// 结束地址也必须能以 64 位表示，因此恰好结束于 2^64 的地址段会被拒绝。
 #[derive(Clone, Debug)]
-pub struct RegRegion(pub Range<usize>);
+pub struct RegRegion(pub Range<u64>);
 
 /// 节点地址空间格式。
+///
+/// 即父节点的 `#address-cells` 和 `#size-cells`，父节点未声明时为 [`RegConfig::DEFAULT`]。
 #[derive(Clone, Copy, Debug)]
 #[repr(C)]
 pub(super) struct RegConfig {
@@ -29,6 +36,7 @@ pub(super) struct RegConfig {
 }
 
 impl RegConfig {
+    /// 规范 2.3.5 节规定的默认值。
     pub const DEFAULT: Self = Self {
         address_cells: 2,
         size_cells: 1,
@@ -48,11 +56,40 @@ impl<'de> Deserialize<'de> for Reg<'_> {
             cursor: match value_deserialzer.cursor {
                 ValueCursor::Prop(_, cursor) => cursor,
                 _ => {
-                    unreachable!("Reg Deserialize should only be called by prop cursor")
+                    return Err(de::Error::invalid_type(
+                        de::Unexpected::Map,
+                        &"reg property",
+                    ));
                 }
             },
         };
 
+        // 检查属性长度是地址段长度的整数倍，且每个地址段都能以 64 位表示
+        let data = inner.cursor.data_on(inner.dtb);
+        let Some(len) = inner.reg.entry_len() else {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Other("too many cells"),
+                &"#address-cells and #size-cells that fit in memory",
+            ));
+        };
+        let whole = match data.len().checked_rem(len) {
+            Some(rem) => rem == 0,
+            None => data.is_empty(),
+        };
+        if !whole {
+            return Err(de::Error::invalid_length(data.len(), &"whole reg entries"));
+        }
+        if len != 0
+            && data
+                .chunks_exact(len)
+                .any(|b| inner.reg.decode(b).is_none())
+        {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Other("address wider than 64 bits"),
+                &"reg entries that fit in 64 bits",
+            ));
+        }
+
         Ok(Self(inner))
     }
 }
@@ -85,38 +122,64 @@ impl Iterator for RegIter<'_> {
     type Item = RegRegion;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let len = BLOCK_LEN * (self.config.address_cells + self.config.size_cells);
-        if self.data.len() >= len {
-            let (current_block, data) = self.data.split_at(len);
-            self.data = data;
-            let mut base = 0;
-            let mut len = 0;
-            let mut block_id = 0;
-            for _ in 0..self.config.address_cells {
-                base = (base << 32)
-                    | u32::from_be_bytes(
-                        current_block[block_id * 4..(block_id + 1) * 4]
-                            .try_into()
-                            .unwrap(),
-                    ) as usize;
-                block_id += 1;
-            }
-            for _ in 0..self.config.size_cells {
-                len = (len << 32)
-                    | u32::from_be_bytes(
-                        current_block[block_id * 4..(block_id + 1) * 4]
-                            .try_into()
-                            .unwrap(),
-                    ) as usize;
-                block_id += 1;
+        // 构造 `Reg` 时已检查过地址段长度
+        let len = self.config.entry_len()?;
+        if len == 0 || self.data.len() < len {
+            return None;
+        }
+        let (current_block, data) = self.data.split_at(len);
+        self.data = data;
+        // 构造 `Reg` 时已检查过每一项，这里不会失败
+        self.config.decode(current_block)
+    }
+}
+
+impl RegConfig {
+    /// 从节点的第一个属性开始扫描，取得节点为子节点声明的地址空间格式。
+    pub fn declared_on(mut cursor: BodyCursor, dtb: RefDtb) -> Result<Self, DtError> {
+        let mut config = Self::DEFAULT;
+        while let Cursor::Prop(c) = cursor.move_on(dtb) {
+            let (name, next) = c.name_on(dtb);
+            match name {
+                "#address-cells" => config.address_cells = c.map_u32_on(dtb)? as usize,
+                "#size-cells" => config.size_cells = c.map_u32_on(dtb)? as usize,
+                _ => {}
             }
-            Some(RegRegion(base..base + len))
-        } else {
-            None
+            cursor = next;
         }
+        Ok(config)
+    }
+
+    /// 一个地址段占用的字节数，超过 `usize` 的表示范围时返回 `None`。
+    #[inline]
+    fn entry_len(&self) -> Option<usize> {
+        self.address_cells
+            .checked_add(self.size_cells)?
+            .checked_mul(BLOCK_LEN)
+    }
+
+    /// 解析一个地址段，地址、长度或结束地址超过 64 位时返回 `None`。
+    ///
+    /// 结束地址恰好为 2^64 时，`Range<u64>` 无法表示，同样返回 `None`。
+    fn decode(&self, block: &[u8]) -> Option<RegRegion> {
+        let (address, size) = block.split_at(BLOCK_LEN * self.address_cells);
+        let base = read_cells(address)?;
+        let end = base.checked_add(read_cells(size)?)?;
+        Some(RegRegion(base..end))
     }
 }
 
+/// 将若干个大端序的 32 位 cell 拼接为一个 64 位数值，超过 64 位时返回 `None`。
+pub(super) fn read_cells(cells: &[u8]) -> Option<u64> {
+    cells.chunks_exact(BLOCK_LEN).try_fold(0u64, |acc, cell| {
+        if acc >> 32 != 0 {
+            return None;
+        }
+        let cell = u32::from_be_bytes(cell.try_into().unwrap());
+        Some((acc << 32) | cell as u64)
+    })
+}
+
 impl Serialize for Reg<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -126,3 +189,75 @@ impl Serialize for Reg<'_> {
         serializer.serialize_bytes(self.0.cursor.data_on(self.0.dtb))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Reg;
+    use crate::{Dtb, DtbPtr, buildin::Node, from_raw_mut};
+    use serde::Serialize;
+
+    #[repr(align(8))]
+    struct SerBuffer {
+        pub data: [u8; 512],
+    }
+
+    #[derive(Serialize)]
+    struct Root {
+        #[serde(rename = "#address-cells")]
+        address_cells: u32,
+        #[serde(rename = "#size-cells")]
+        size_cells: u32,
+        wide: Dev<(u32, u32, u32, u32)>,
+        narrow: Dev<(u32, u32, u32, u32)>,
+        partial: Dev<(u32, u32, u32)>,
+        top: Dev<(u32, u32, u32, u32)>,
+    }
+    #[derive(Serialize)]
+    struct Dev<T> {
+        reg: T,
+    }
+
+    #[test]
+    fn test_reg_u64() {
+        let mut buf = SerBuffer { data: [0; 512] };
+        let root = Root {
+            address_cells: 3,
+            size_cells: 1,
+            wide: Dev {
+                reg: (0x1, 0x2, 0x3, 0x10),
+            },
+            narrow: Dev {
+                reg: (0x0, 0x2, 0x3, 0x10),
+            },
+            partial: Dev {
+                reg: (0x0, 0x2, 0x3),
+            },
+            top: Dev {
+                reg: (0x0, 0xffff_ffff, 0xf000_0000, 0x1000_0000),
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let reg = |path: &str| {
+            node.find(path)
+                .unwrap()
+                .get_prop("reg")
+                .unwrap()
+                .try_deserialize::<Reg>()
+        };
+
+        // 高位 cell 为零时可以用 64 位表示
+        let narrow = reg("/narrow").unwrap();
+        let mut iter = narrow.iter();
+        assert_eq!(iter.next().unwrap().0, 0x2_0000_0003..0x2_0000_0013);
+        assert!(iter.next().is_none());
+        // 地址超过 64 位
+        assert!(reg("/wide").is_err());
+        // 属性长度不是地址段长度的整数倍
+        assert!(reg("/partial").is_err());
+        // 结束地址恰好为 2^64，无法以 `Range<u64>` 表示
+        assert!(reg("/top").is_err());
+    }
+}
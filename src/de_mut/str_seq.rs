@@ -1,6 +1,6 @@
-﻿use super::{PropCursor, RefDtb, ValueCursor};
+﻿use super::{DtError, PropCursor, RefDtb, ValueCursor};
 use core::fmt::Debug;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de};
 
 /// 一组 '\0' 分隔字符串的映射。
 ///
@@ -26,6 +26,9 @@ pub struct StrSeqIter<'de> {
     data: &'de [u8],
 }
 
+/// 将 '\0' 分隔字符串组作为序列交给访问者。
+pub(super) struct StrSeqAccess<'de>(StrSeqIter<'de>);
+
 impl<'de> Deserialize<'de> for StrSeq<'_> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -38,7 +41,10 @@ impl<'de> Deserialize<'de> for StrSeq<'_> {
             cursor: match value_deserialzer.cursor {
                 ValueCursor::Prop(_, cursor) => cursor,
                 _ => {
-                    unreachable!("StrSeq Deserialize should only be called by prop cursor")
+                    return Err(de::Error::invalid_type(
+                        de::Unexpected::Map,
+                        &"string list property",
+                    ));
                 }
             },
         };
@@ -91,6 +97,29 @@ impl<'de> Iterator for StrSeqIter<'de> {
     }
 }
 
+impl<'de> StrSeqAccess<'de> {
+    /// 调用者保证 `data` 中都是有效的 UTF-8 字符串。
+    pub fn new(data: &'de [u8]) -> Self {
+        Self(StrSeqIter { data })
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for StrSeqAccess<'de> {
+    type Error = DtError;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.0.next() {
+            Some(s) => seed
+                .deserialize(de::value::BorrowedStrDeserializer::new(s))
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
 impl Serialize for StrSeq<'_> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
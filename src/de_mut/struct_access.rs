@@ -1,5 +1,5 @@
 use super::cursor::MultiNodeCursor;
-use super::{BodyCursor, Cursor, PropCursor, ValueCursor, ValueDeserializer};
+use super::{BodyCursor, Cursor, PropCursor, RegConfig, ValueCursor, ValueDeserializer};
 use crate::error::Error as DtError;
 use serde::de;
 
@@ -90,15 +90,6 @@ impl<'de> de::MapAccess<'de> for StructAccess<'de, '_> {
                 Cursor::Prop(c) => {
                     let (name, next) = c.name_on(self.de.dtb);
                     self.de.cursor = ValueCursor::Body(next);
-                    match name {
-                        "#address-cells" => {
-                            self.de.reg.address_cells = c.map_u32_on(self.de.dtb)? as usize;
-                        }
-                        "#size-cells" => {
-                            self.de.reg.size_cells = c.map_u32_on(self.de.dtb)? as usize;
-                        }
-                        _ => {}
-                    }
                     if check_contains(name) {
                         self.temp = Temp::Prop(origin_cursor, c);
                         break name;
@@ -122,9 +113,14 @@ impl<'de> de::MapAccess<'de> for StructAccess<'de, '_> {
         if let StructAccessType::Map(ref mut flag) = self.access_type {
             if !*flag {
                 *flag = true;
+                // 只有完整的节点才能作为根；`NodeSeqItem` 等给出的节点内容没有节点的起始位置。
+                if let ValueCursor::Body(_) = self.de.cursor {
+                    return Err(DtError::unexpected_node("node", self.de.file_index_on()));
+                }
                 return seed.deserialize(&mut ValueDeserializer {
                     dtb: self.de.dtb,
                     reg: self.de.reg,
+                    child_reg: self.de.child_reg,
                     cursor: self.de.cursor,
                 });
             }
@@ -135,12 +131,14 @@ impl<'de> de::MapAccess<'de> for StructAccess<'de, '_> {
                 match self.access_type {
                     StructAccessType::Map(_) => seed.deserialize(&mut ValueDeserializer {
                         dtb: self.de.dtb,
-                        reg: self.de.reg,
+                        reg: self.de.child_reg,
+                        child_reg: RegConfig::DEFAULT,
                         cursor: ValueCursor::NodeIn(*result),
                     }),
                     StructAccessType::Struct(_) => seed.deserialize(&mut ValueDeserializer {
                         dtb: self.de.dtb,
-                        reg: self.de.reg,
+                        reg: self.de.child_reg,
+                        child_reg: RegConfig::DEFAULT,
                         cursor: ValueCursor::NodeIn(*result),
                     }),
                     _ => unreachable!(),
@@ -151,6 +149,7 @@ impl<'de> de::MapAccess<'de> for StructAccess<'de, '_> {
                 seed.deserialize(&mut ValueDeserializer {
                     dtb: self.de.dtb,
                     reg: self.de.reg,
+                    child_reg: self.de.child_reg,
                     cursor: ValueCursor::Prop(origin_cursor, cursor),
                 })
             }
@@ -189,6 +188,7 @@ impl<'de> de::SeqAccess<'de> for StructAccess<'de, '_> {
                     seed.deserialize(&mut ValueDeserializer {
                         dtb: self.de.dtb,
                         reg: self.de.reg,
+                        child_reg: RegConfig::DEFAULT,
                         cursor: ValueCursor::Body(prev_cursor),
                     })
                     .map(Some)
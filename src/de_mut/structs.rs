@@ -43,6 +43,60 @@ impl DtbPtr {
         }
         res
     }
+
+    /// 获得内存保留块迭代器。
+    pub fn memory_reservations(&self) -> MemRsvIter<'_> {
+        MemRsvIter::new(unsafe { &*(self.0 as *const Header) })
+    }
+}
+
+/// 内存保留块迭代器。
+///
+/// 每项是一段保留内存的起始地址和长度。
+/// 遇到全零的终止项，或设备树剩余部分不足一项时结束，不会越过设备树的总长度。
+#[derive(Clone)]
+pub struct MemRsvIter<'a> {
+    data: &'a [u8],
+}
+
+/// 内存保留块中一项的长度。
+const MEM_RSV_ENTRY_LEN: usize = 2 * core::mem::size_of::<u64>();
+
+impl<'a> MemRsvIter<'a> {
+    fn new(header: &'a Header) -> Self {
+        let total_size = u32::from_be(header.total_size) as usize;
+        let off_mem_rsvmap = u32::from_be(header.off_mem_rsvmap) as usize;
+        let data = match total_size.checked_sub(off_mem_rsvmap) {
+            Some(len) => unsafe {
+                let ptr = (header as *const Header as *const u8).add(off_mem_rsvmap);
+                core::slice::from_raw_parts(ptr, len)
+            },
+            None => &[],
+        };
+        Self { data }
+    }
+
+    /// 从共享的设备树映射构造，迭代器的生命周期与映射相同。
+    pub(super) fn on(dtb: RefDtb<'a>) -> Self {
+        Self::new(unsafe { &*(dtb.borrow().ptr as *const Header) })
+    }
+}
+
+impl Iterator for MemRsvIter<'_> {
+    type Item = (u64, u64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (entry, rest) = self.data.split_first_chunk::<MEM_RSV_ENTRY_LEN>()?;
+        let (address, size) = entry.split_at(MEM_RSV_ENTRY_LEN / 2);
+        let address = u64::from_be_bytes(address.try_into().unwrap());
+        let size = u64::from_be_bytes(size.try_into().unwrap());
+        if address == 0 && size == 0 {
+            self.data = &[];
+            return None;
+        }
+        self.data = rest;
+        Some((address, size))
+    }
 }
 
 /// 对齐到 4 字节的设备树结构块。
@@ -152,6 +206,11 @@ impl Dtb {
         RefCell::new(self)
     }
 
+    /// 获得内存保留块迭代器。
+    pub fn memory_reservations(&self) -> MemRsvIter<'_> {
+        MemRsvIter::new(unsafe { &*(self.ptr as *const Header) })
+    }
+
     /// 获取结构块的相对偏移。
     pub fn off_dt_struct(&self) -> usize {
         u32::from_be(unsafe { &*(self.ptr as *const Header) }.off_dt_struct) as _
@@ -159,3 +218,52 @@ impl Dtb {
 }
 
 pub(super) type RefDtb<'a> = &'a RefCell<Dtb>;
+
+#[cfg(test)]
+mod tests {
+    use super::{Dtb, DtbPtr};
+
+    #[repr(align(8))]
+    struct AlignedBuffer {
+        pub data: [u8; 128],
+    }
+
+    /// 按大端序依次写入首部和各块，`words` 中的每项占 4 字节。
+    fn build(words: &[u32]) -> AlignedBuffer {
+        let mut buf = AlignedBuffer { data: [0; 128] };
+        for (chunk, word) in buf.data.chunks_exact_mut(4).zip(words) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        buf
+    }
+
+    #[test]
+    fn test_memory_reservations() {
+        // 首部之后是两项保留内存和终止项，然后是只有根节点的结构块。
+        #[rustfmt::skip]
+        let mut buf = build(&[
+            0xd00dfeed, 104, 88, 104, 40, 17, 16, 0, 0, 16,
+            0, 0x8000_0000, 0, 0x20_0000,
+            0x1, 0x2000_0000, 0, 0x1000,
+            0, 0, 0, 0,
+            1, 0, 2, 9,
+        ]);
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let entries: Vec<_> = ptr.memory_reservations().collect();
+        assert_eq!(entries, [(0x8000_0000, 0x20_0000), (0x1_2000_0000, 0x1000)]);
+        let dtb = Dtb::from(ptr).share();
+        assert_eq!(dtb.borrow().memory_reservations().count(), 2);
+
+        // 保留块位于末尾且缺少终止项，迭代在总长度处结束。
+        #[rustfmt::skip]
+        let mut buf = build(&[
+            0xd00dfeed, 76, 40, 56, 56, 17, 16, 0, 0, 16,
+            1, 0, 2, 9,
+            0, 0x8000_0000, 0, 0x1000,
+            0xffff_ffff,
+        ]);
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let entries: Vec<_> = ptr.memory_reservations().collect();
+        assert_eq!(entries, [(0x8000_0000, 0x1000)]);
+    }
+}
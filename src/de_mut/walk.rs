@@ -0,0 +1,316 @@
+use super::{BodyCursor, Cursor, DtError, RefDtb, RegConfig, node::Node};
+
+/// 遍历时允许的最大深度，与 Linux 的 `FDT_MAX_DEPTH` 相同。
+pub(crate) const MAX_DEPTH: usize = 64;
+
+/// 深度优先遍历中，访问一个节点后的动作。
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WalkAction {
+    /// 继续遍历，包括这个节点的子节点。
+    Continue,
+    /// 跳过这个节点的子节点，继续遍历其后的节点。
+    SkipChildren,
+    /// 停止遍历。
+    Stop,
+}
+
+/// 深度优先遍历的状态。
+///
+/// 每次前进前，决定是否进入上一个返回的节点。
+///
+/// 每层只保存祖先的开始处，各层的地址空间格式在需要时从祖先的属性重新扫描，以减少栈上的占用。
+pub(crate) struct Walker<'de> {
+    dtb: RefDtb<'de>,
+    /// 深度为 0 的节点的地址空间格式。
+    root_reg: RegConfig,
+    /// 深度为 `depth` 的节点的父节点声明的地址空间格式。
+    reg: RegConfig,
+    /// `titles[i]` 是上一个返回的节点在深度 `i` 处的祖先的开始处。
+    titles: [BodyCursor; MAX_DEPTH],
+    cursor: BodyCursor,
+    /// 下一个遇到的节点的深度。
+    depth: usize,
+    /// 上一个返回的节点的内容起点和为子节点声明的地址空间格式。
+    last: Option<(BodyCursor, RegConfig)>,
+    finished: bool,
+}
+
+impl<'de> Walker<'de> {
+    /// 从节点开始遍历，这个节点的深度为 0。
+    pub fn new(node: &Node<'de>) -> Self {
+        Self {
+            dtb: node.dtb,
+            root_reg: node.reg,
+            reg: node.reg,
+            titles: [node.cursor; MAX_DEPTH],
+            cursor: node.cursor,
+            depth: 0,
+            last: None,
+            finished: false,
+        }
+    }
+
+    /// 获得下一个节点及其深度，`enter` 为 `false` 时跳过上一个节点的子节点。
+    pub fn next_node(&mut self, enter: bool) -> Option<Result<(usize, Node<'de>), DtError>> {
+        if self.finished {
+            return None;
+        }
+        let dtb = self.dtb;
+        if let Some((body, child_reg)) = self.last.take() {
+            self.cursor = body;
+            if enter {
+                self.depth += 1;
+                self.reg = child_reg;
+            } else if self.depth == 0 {
+                self.finished = true;
+                return None;
+            } else {
+                self.cursor.escape_from(dtb);
+            }
+        }
+        loop {
+            match self.cursor.move_on(dtb) {
+                Cursor::Title(c) => {
+                    let (_, body) = c.split_on(dtb);
+                    if self.depth >= MAX_DEPTH {
+                        self.finished = true;
+                        let file_index = self.cursor.file_index_on(dtb);
+                        return Some(Err(DtError::nesting_too_deep(MAX_DEPTH, file_index)));
+                    }
+                    return match Node::new_on(dtb, self.reg, self.cursor) {
+                        Ok(node) => {
+                            self.titles[self.depth] = self.cursor;
+                            self.last = Some((body, node.child_reg));
+                            Some(Ok((self.depth, node)))
+                        }
+                        Err(e) => {
+                            self.finished = true;
+                            Some(Err(e))
+                        }
+                    };
+                }
+                Cursor::Prop(c) => self.cursor = c.name_on(dtb).1,
+                Cursor::End => {
+                    self.cursor.step_n(1);
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        self.finished = true;
+                        return None;
+                    }
+                    match self.reg_at(self.depth) {
+                        Ok(reg) => self.reg = reg,
+                        Err(e) => {
+                            self.finished = true;
+                            return Some(Err(e));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// 遍历到与 `target` 相同的节点，返回它的深度；遍历结束仍未找到时返回 `None`。
+    pub fn seek(&mut self, target: &Node) -> Result<Option<usize>, DtError> {
+        while let Some(item) = self.next_node(true) {
+            let (depth, node) = item?;
+            if node.is_same(target) {
+                return Ok(Some(depth));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 获得上一个返回的节点在深度 `depth` 处的祖先。
+    ///
+    /// `depth` 不能超过上一个节点的深度，等于时得到上一个节点本身。
+    pub fn ancestor(&self, depth: usize) -> Result<Node<'de>, DtError> {
+        Node::new_on(self.dtb, self.reg_at(depth)?, self.titles[depth])
+    }
+
+    /// 获得深度为 `depth` 的祖先的父节点声明的地址空间格式。
+    fn reg_at(&self, depth: usize) -> Result<RegConfig, DtError> {
+        let Some(parent) = depth.checked_sub(1) else {
+            return Ok(self.root_reg);
+        };
+        let mut title = self.titles[parent];
+        match title.move_on(self.dtb) {
+            Cursor::Title(c) => RegConfig::declared_on(c.split_on(self.dtb).1, self.dtb),
+            _ => unreachable!("ancestor's cursor should on its start"),
+        }
+    }
+}
+
+impl<'de> Node<'de> {
+    /// 深度优先遍历以本节点为根的子树，对每个节点调用 `f`。
+    ///
+    /// `f` 的参数是节点相对本节点的深度和节点对象，本节点的深度为 0。
+    /// 遍历按结构块中的节点开始和结束标记计算深度，不使用递归，
+    /// 因此嵌套很深的设备树也不会耗尽栈空间。
+    /// 与 Linux 相同，最多允许 64 层嵌套，遇到更深的节点时返回错误。
+    pub fn walk<F>(&self, mut f: F) -> Result<(), DtError>
+    where
+        F: FnMut(usize, &Node<'de>) -> WalkAction,
+    {
+        let mut walker = Walker::new(self);
+        let mut action = WalkAction::Continue;
+        while let Some(item) = walker.next_node(action != WalkAction::SkipChildren) {
+            let (depth, node) = item?;
+            action = f(depth, &node);
+            if action == WalkAction::Stop {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_DEPTH, WalkAction};
+    use crate::{
+        Dtb, DtbPtr,
+        buildin::Node,
+        buildin::Reg,
+        error::{Error, ErrorType},
+        from_raw_mut,
+    };
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[repr(align(8))]
+    struct AlignedBuffer {
+        pub data: [u8; RAW_DEVICE_TREE.len()],
+    }
+    #[test]
+    fn test_walk() {
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let mut count = 0;
+        let mut max_depth = 0;
+        node.walk(|depth, _| {
+            count += 1;
+            max_depth = max_depth.max(depth);
+            WalkAction::Continue
+        })
+        .unwrap();
+        assert_eq!(count, 30);
+        // `/cpus/cpu-map/cluster0/core0`
+        assert_eq!(max_depth, 4);
+
+        // 跳过 `/soc` 的 14 个子节点。
+        let mut count = 0;
+        node.walk(|_, node| {
+            count += 1;
+            match node.name() {
+                "soc" => WalkAction::SkipChildren,
+                _ => WalkAction::Continue,
+            }
+        })
+        .unwrap();
+        assert_eq!(count, 16);
+
+        // 找到节点后停止，得到的节点与按路径查找的相同。
+        let mut found = None;
+        node.walk(|depth, node| match node.name() {
+            "serial@10000000" => {
+                assert_eq!(depth, 2);
+                found = Some(node.clone());
+                WalkAction::Stop
+            }
+            _ => WalkAction::Continue,
+        })
+        .unwrap();
+        let serial = found.unwrap();
+        assert!(serial.is_same(&node.find("/soc/serial@10000000").unwrap()));
+        let reg = serial.get_prop("reg").unwrap().deserialize::<Reg>();
+        assert_eq!(reg.iter().next().unwrap().0.start, 0x1000_0000);
+
+        // 从子树的根开始遍历，深度相对子树的根计算。
+        let cpus = node.find("/cpus").unwrap();
+        let mut names = Vec::new();
+        cpus.walk(|depth, node| {
+            names.push((depth, node.name()));
+            WalkAction::Continue
+        })
+        .unwrap();
+        assert_eq!(
+            names,
+            [
+                (0, "cpus"),
+                (1, "cpu@0"),
+                (2, "interrupt-controller"),
+                (1, "cpu-map"),
+                (2, "cluster0"),
+                (3, "core0"),
+            ]
+        );
+    }
+
+    /// 构造根节点下嵌套 `depth` 层名为 `n` 的节点的设备树，以 8 字节对齐。
+    fn nested(depth: usize) -> Vec<u64> {
+        let mut structure = vec![1, 0];
+        for _ in 0..depth {
+            structure.extend([1, u32::from_be_bytes(*b"n\0\0\0")]);
+        }
+        structure.extend(core::iter::repeat_n(2, depth + 1));
+        structure.push(9);
+        let size = structure.len() as u32 * 4;
+        let mut words = vec![0xd00dfeed, 56 + size, 56, 56 + size, 40, 17, 16, 0, 0, size];
+        words.extend([0; 4]);
+        words.extend(structure);
+        words.resize(words.len().next_multiple_of(2), 0);
+        words
+            .chunks_exact(2)
+            .map(|pair| {
+                let bytes = [pair[0].to_be_bytes(), pair[1].to_be_bytes()].concat();
+                u64::from_ne_bytes(bytes.try_into().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_nesting_too_deep() {
+        let mut data = nested(100);
+        let ptr = DtbPtr::from_raw(data.as_mut_ptr().cast()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        // 访问到第 64 层后返回错误，而不是跳过更深的节点。
+        let mut count = 0;
+        let result = node.walk(|_, _| {
+            count += 1;
+            WalkAction::Continue
+        });
+        assert_eq!(count, MAX_DEPTH);
+        assert!(matches!(
+            result,
+            Err(Error::Typed {
+                error_type: ErrorType::NestingTooDeep { max_depth: 64 },
+                ..
+            })
+        ));
+        // `search` 在第 64 层后停止。
+        let mut count = 0;
+        node.search(&mut |_| count += 1);
+        assert_eq!(count, MAX_DEPTH);
+        assert!(node.find_by_phandle(1).is_none());
+
+        // 从根节点向下查找的辅助函数同样不会耗尽栈空间。
+        let path = "/n".repeat(80);
+        let deep = node.find(&path).unwrap();
+        assert!(deep.parent().is_none());
+        assert!(deep.path(&mut String::new()).is_err());
+        let shallow = node.find("/n/n").unwrap();
+        assert_eq!(shallow.parent().unwrap().name(), "n");
+        let mut path = String::new();
+        shallow.path(&mut path).unwrap();
+        assert_eq!(path, "/n/n");
+    }
+}
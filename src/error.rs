@@ -82,7 +82,27 @@ pub enum ErrorType {
     BuildInTypeParseFailed {
         expected: &'static str,
     },
+    TypeMismatch {
+        expected: &'static str,
+        found_len: usize,
+    },
+    UnexpectedNode {
+        expected: &'static str,
+    },
+    UnexpectedProp {
+        expected: &'static str,
+    },
     Utf8(core::str::Utf8Error),
+    AddressOverflow,
+    BufferTooSmall {
+        required_length: usize,
+    },
+    PhandleNotFound {
+        phandle: u32,
+    },
+    NestingTooDeep {
+        max_depth: usize,
+    },
 }
 
 impl Error {
@@ -280,6 +300,30 @@ impl Error {
         }
     }
     #[inline]
+    pub fn type_mismatch(expected: &'static str, found_len: usize, file_index: usize) -> Error {
+        Error::Typed {
+            error_type: ErrorType::TypeMismatch {
+                expected,
+                found_len,
+            },
+            file_index,
+        }
+    }
+    #[inline]
+    pub fn unexpected_node(expected: &'static str, file_index: usize) -> Error {
+        Error::Typed {
+            error_type: ErrorType::UnexpectedNode { expected },
+            file_index,
+        }
+    }
+    #[inline]
+    pub fn unexpected_prop(expected: &'static str, file_index: usize) -> Error {
+        Error::Typed {
+            error_type: ErrorType::UnexpectedProp { expected },
+            file_index,
+        }
+    }
+    #[inline]
     pub fn utf8(error: core::str::Utf8Error, file_index: usize) -> Error {
         Error::Typed {
             error_type: ErrorType::Utf8(error),
@@ -287,6 +331,34 @@ impl Error {
         }
     }
     #[inline]
+    pub fn address_overflow(file_index: usize) -> Error {
+        Error::Typed {
+            error_type: ErrorType::AddressOverflow,
+            file_index,
+        }
+    }
+    #[inline]
+    pub fn buffer_too_small(required_length: usize) -> Error {
+        Error::Typed {
+            error_type: ErrorType::BufferTooSmall { required_length },
+            file_index: 0,
+        }
+    }
+    #[inline]
+    pub fn phandle_not_found(phandle: u32, file_index: usize) -> Error {
+        Error::Typed {
+            error_type: ErrorType::PhandleNotFound { phandle },
+            file_index,
+        }
+    }
+    #[inline]
+    pub fn nesting_too_deep(max_depth: usize, file_index: usize) -> Error {
+        Error::Typed {
+            error_type: ErrorType::NestingTooDeep { max_depth },
+            file_index,
+        }
+    }
+    #[inline]
     pub fn expected_struct_begin() -> Error {
         Error::Typed {
             error_type: ErrorType::ExpectStructBegin,
@@ -29,12 +29,13 @@ mod de_mut;
 mod tag;
 
 pub use value::compatible::Compatible;
+pub use value::status::Status;
 
 #[doc(inline)]
 pub use de::from_raw;
 
 #[doc(inline)]
-pub use de_mut::{Dtb, DtbPtr, buildin, from_raw_mut};
+pub use de_mut::{Dtb, DtbPtr, MemRsvIter, buildin, from_raw_mut};
 
 #[doc(inline)]
 pub use error::Result;
@@ -6,28 +6,46 @@ pub mod string_block;
 use crate::common::*;
 use crate::ser::patch::Patch;
 
-// TODO: set reverse map
-const RSVMAP_LEN: usize = 16;
+/// Length of an entry in the memory reservation block.
+pub(crate) const RSVMAP_ENTRY_LEN: usize = 16;
 
 /// Serialize the data to dtb, with a list fof Patch, write to the `writer`.
 ///
 /// We do run-twice on convert, first time to generate string block, second time todo real
 /// structure.
 pub fn to_dtb<'se, T>(data: &T, list: &'se [Patch<'se>], writer: &'se mut [u8]) -> Result<(), Error>
+where
+    T: serde::ser::Serialize,
+{
+    to_dtb_with_reservations(data, list, &[], writer)
+}
+
+/// Serialize the data to dtb like [`to_dtb`], and write `reservations`,
+/// a list of (address, size) pairs, to the memory reservation block.
+///
+/// When `data` is a [`Node`](crate::buildin::Node), the memory reservations of its source blob
+/// are carried over, before `reservations`.
+pub fn to_dtb_with_reservations<'se, T>(
+    data: &T,
+    list: &'se [Patch<'se>],
+    reservations: &[(u64, u64)],
+    writer: &'se mut [u8],
+) -> Result<(), Error>
 where
     T: serde::ser::Serialize,
 {
     writer.iter_mut().for_each(|x| *x = 0);
 
     let mut offset: usize = 0;
-    {
+    let source_len = {
         let mut dst = crate::ser::pointer::Pointer::new(None);
         let mut patch_list = crate::ser::patch::PatchList::new(list);
         let mut block = crate::ser::string_block::StringBlock::new(writer, &mut offset);
-        let mut ser =
+        let mut inner =
             crate::ser::serializer::SerializerInner::new(&mut dst, &mut block, &mut patch_list);
-        let ser = crate::ser::serializer::Serializer::new(&mut ser);
+        let ser = crate::ser::serializer::Serializer::new(&mut inner);
         data.serialize(ser)?;
+        inner.reservations
     };
     list.iter().for_each(|patch| patch.init());
     // Write from bottom to top, to avoid overlap.
@@ -37,25 +55,32 @@ where
     }
     // TODO: make sure no out of bound.
 
+    // Leave a zero entry at the end of the memory reservation block.
+    let rsvmap_len = (source_len + reservations.len() + 1) * RSVMAP_ENTRY_LEN;
     let writer_len = writer.len();
     let (data_block, string_block) = writer.split_at_mut(writer.len() - offset);
-    let (header, data_block) = data_block.split_at_mut(HEADER_LEN as usize + RSVMAP_LEN);
+    let (header, data_block) = data_block.split_at_mut(HEADER_LEN as usize + rsvmap_len);
+    let (header, rsvmap) = header.split_at_mut(HEADER_LEN as usize);
     let struct_len = {
         let mut patch_list = crate::ser::patch::PatchList::new(list);
         let mut block = crate::ser::string_block::StringBlock::new(string_block, &mut offset);
         let mut dst = crate::ser::pointer::Pointer::new(Some(data_block));
-        let mut ser =
+        let mut inner =
             crate::ser::serializer::SerializerInner::new(&mut dst, &mut block, &mut patch_list);
-        let ser = crate::ser::serializer::Serializer::new(&mut ser);
-        data.serialize(ser)?
-    }
-    .1;
+        inner.rsvmap = Some(rsvmap);
+        let ser = crate::ser::serializer::Serializer::new(&mut inner);
+        let struct_len = data.serialize(ser)?.1;
+        for &(address, size) in reservations {
+            inner.reserve(address, size);
+        }
+        struct_len
+    };
     // Make header
     {
         let header = unsafe { &mut *(header.as_mut_ptr() as *mut Header) };
         header.magic = u32::from_be(DEVICE_TREE_MAGIC);
         header.total_size = u32::from_be(writer_len as u32);
-        header.off_dt_struct = u32::from_be(HEADER_LEN + RSVMAP_LEN as u32);
+        header.off_dt_struct = u32::from_be(HEADER_LEN + rsvmap_len as u32);
         header.off_dt_strings = u32::from_be((writer_len - offset) as u32);
         header.off_mem_rsvmap = u32::from_be(HEADER_LEN);
         header.version = u32::from_be(SUPPORTED_VERSION);
@@ -88,3 +113,60 @@ impl serde::ser::Error for Error {
         Self::Unknown
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dtb, DtbPtr, buildin::Node, from_raw_mut};
+    use serde::Serialize;
+
+    #[repr(align(8))]
+    struct SerBuffer {
+        pub data: [u8; 256],
+    }
+    #[test]
+    fn test_reservations() {
+        #[derive(Serialize)]
+        struct Root {
+            model: &'static str,
+        }
+        let mut buf = SerBuffer { data: [0; 256] };
+        let reservations = [(0x8000_0000, 0x20_0000), (0x1_0000_0000, 0x1000)];
+        super::to_dtb_with_reservations(&Root { model: "test" }, &[], &reservations, &mut buf.data)
+            .unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let entries: Vec<_> = ptr.memory_reservations().collect();
+        assert_eq!(entries, reservations);
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        assert_eq!(
+            node.get_prop("model").unwrap().deserialize::<&str>(),
+            "test"
+        );
+
+        // Re-encoding a node keeps reservations of its source blob.
+        let mut out = SerBuffer { data: [0; 256] };
+        super::to_dtb_with_reservations(&node, &[], &[(0x9000_0000, 0x100)], &mut out.data)
+            .unwrap();
+        let ptr = DtbPtr::from_raw(out.data.as_mut_ptr()).unwrap();
+        let entries: Vec<_> = ptr.memory_reservations().collect();
+        assert_eq!(
+            entries,
+            [
+                (0x8000_0000, 0x20_0000),
+                (0x1_0000_0000, 0x1000),
+                (0x9000_0000, 0x100)
+            ]
+        );
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        assert_eq!(
+            node.get_prop("model").unwrap().deserialize::<&str>(),
+            "test"
+        );
+
+        let mut out = SerBuffer { data: [0; 256] };
+        super::to_dtb(&node, &[], &mut out.data).unwrap();
+        let ptr = DtbPtr::from_raw(out.data.as_mut_ptr()).unwrap();
+        assert_eq!(ptr.memory_reservations().count(), 3);
+    }
+}
@@ -2,7 +2,7 @@ use super::patch::{Patch, PatchList};
 use super::pointer::Pointer;
 use super::string_block::StringBlock;
 use crate::common::*;
-use crate::ser::Error;
+use crate::ser::{Error, RSVMAP_ENTRY_LEN};
 
 // The enum for current parsing type.
 #[derive(Clone, Copy, Debug)]
@@ -17,6 +17,10 @@ pub struct SerializerInner<'se> {
     pub dst: &'se mut Pointer<'se>,
     string_block: &'se mut StringBlock<'se>,
     patch_list: &'se mut PatchList<'se>,
+    /// Memory reservation block to write entries to, `None` if only counting them.
+    pub rsvmap: Option<&'se mut [u8]>,
+    /// Count of memory reservation entries written so far.
+    pub reservations: usize,
 }
 
 /// Serializer
@@ -41,8 +45,24 @@ impl<'se> SerializerInner<'se> {
             dst,
             string_block,
             patch_list,
+            rsvmap: None,
+            reservations: 0,
         }
     }
+
+    /// Append an entry to the memory reservation block.
+    pub fn reserve(&mut self, address: u64, size: u64) {
+        if let Some(entry) = self.rsvmap.as_mut().and_then(|rsvmap| {
+            rsvmap
+                .chunks_exact_mut(RSVMAP_ENTRY_LEN)
+                .nth(self.reservations)
+        }) {
+            let (address_bytes, size_bytes) = entry.split_at_mut(RSVMAP_ENTRY_LEN / 2);
+            address_bytes.copy_from_slice(&address.to_be_bytes());
+            size_bytes.copy_from_slice(&size.to_be_bytes());
+        }
+        self.reservations += 1;
+    }
 }
 
 impl<'a, 'se> Serializer<'a, 'se> {
@@ -446,13 +466,23 @@ impl<'se> serde::ser::Serializer for Serializer<'_, 'se> {
     where
         T: serde::ser::Serialize + ?Sized,
     {
-        use crate::de_mut::node::{Node, NodeItem};
+        use crate::de_mut::node::{Node, NodeContent, NodeItem};
         use crate::de_mut::{NODE_NAME, NODE_NODE_ITEM_NAME};
         use core::ptr::addr_of;
         match name {
             NODE_NODE_ITEM_NAME => {
                 let v = unsafe { &*(addr_of!(v) as *const &NodeItem<'se>) };
-                self.serialize_newtype_struct(NODE_NAME, &v.deserialize::<Node>())
+                serde::Serialize::serialize(&v.deserialize::<Node>(), self)
+            }
+            NODE_NAME => {
+                // Carry over reservations of the source blob when re-encoding a root node.
+                if self.current_dep == 0 {
+                    let node = unsafe { &*(addr_of!(v) as *const &NodeContent) };
+                    for (address, size) in node.0.memory_reservations() {
+                        self.ser.reserve(address, size);
+                    }
+                }
+                v.serialize(self)
             }
             _ => todo!(),
         }
@@ -7,11 +7,14 @@ impl<'de> Node<'de> {
         self.find("/chosen")
     }
     /// Get /chosen/stdin-path
+    ///
+    /// Only the first string is used if the property is a string list.
     pub fn chosen_stdin_path(&self) -> Option<&'de str> {
         let result = self
             .chosen()?
             .get_prop("stdin-path")?
-            .deserialize::<StrSeq>()
+            .try_deserialize::<StrSeq>()
+            .ok()?
             .iter()
             .next()?;
         if let Some(pos) = result.find(':') {
@@ -21,11 +24,14 @@ impl<'de> Node<'de> {
         }
     }
     /// Get /chosen/stdout-path
+    ///
+    /// Only the first string is used if the property is a string list.
     pub fn chosen_stdout_path(&self) -> Option<&'de str> {
         let result = self
             .chosen()?
             .get_prop("stdout-path")?
-            .deserialize::<StrSeq>()
+            .try_deserialize::<StrSeq>()
+            .ok()?
             .iter()
             .next()?;
         if let Some(pos) = result.find(':') {
@@ -60,4 +66,35 @@ mod tests {
         assert!(node.chosen().is_some());
         assert_eq!(node.chosen_stdout_path(), Some("serial3"));
     }
+    #[test]
+    fn test_chosen_string_list() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            chosen: Chosen,
+        }
+        #[derive(Serialize)]
+        struct Chosen {
+            #[serde(rename = "stdout-path")]
+            stdout_path: &'static str,
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 256],
+        }
+        let mut buf = SerBuffer { data: [0; 256] };
+        let root = Root {
+            chosen: Chosen {
+                stdout_path: "serial0:115200n8\0serial1",
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        // Only the first string of a string list is used.
+        assert_eq!(node.chosen_stdout_path(), Some("serial0"));
+    }
 }
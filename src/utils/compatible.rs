@@ -0,0 +1,119 @@
+use crate::{Compatible, buildin::Node, de_mut::walk::Walker, error::Error};
+
+/// Iterator over nodes whose `compatible` list contains any of the given strings,
+/// in tree order.
+///
+/// If the structure block is malformed or nested too deep, it yields the error and ends.
+pub struct FindCompatible<'de, 'a> {
+    walker: Walker<'de>,
+    compatibles: &'a [&'a str],
+}
+
+impl<'de> Node<'de> {
+    /// Get the `compatible` property of this node.
+    pub fn compatible(&self) -> Option<Compatible<'de>> {
+        self.get_prop("compatible")?.try_deserialize().ok()
+    }
+
+    /// Find every node in this subtree, including this node itself,
+    /// whose `compatible` list contains any of `compatibles`.
+    pub fn find_compatible<'a>(&self, compatibles: &'a [&'a str]) -> FindCompatible<'de, 'a> {
+        FindCompatible {
+            walker: Walker::new(self),
+            compatibles,
+        }
+    }
+
+    /// Find the node in this subtree that matches `compatibles` best.
+    ///
+    /// Nodes are ranked by [`Compatible::position`] of the first matching string,
+    /// and the first one in tree order wins a tie.
+    /// Returns `Ok(None)` if no node matches.
+    pub fn find_best_compatible(&self, compatibles: &[&str]) -> Result<Option<Node<'de>>, Error> {
+        let mut best: Option<(usize, Node<'de>)> = None;
+        for node in self.find_compatible(compatibles) {
+            let node = node?;
+            let position = node
+                .compatible()
+                .and_then(|compatible| compatible.position(compatibles));
+            if let Some(position) = position
+                && best.as_ref().is_none_or(|(best, _)| position < *best)
+            {
+                best = Some((position, node));
+            }
+        }
+        Ok(best.map(|(_, node)| node))
+    }
+}
+
+impl<'de> Iterator for FindCompatible<'de, '_> {
+    type Item = Result<Node<'de>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (_, node) = match self.walker.next_node(true)? {
+                Ok(item) => item,
+                Err(e) => return Some(Err(e)),
+            };
+            let matched = node
+                .compatible()
+                .and_then(|compatible| compatible.position(self.compatibles));
+            if matched.is_some() {
+                return Some(Ok(node));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dtb, DtbPtr, buildin::Node, buildin::StrSeq, from_raw_mut};
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[test]
+    fn test_find_compatible() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        assert_eq!(node.find_compatible(&["virtio,mmio"]).count(), 8);
+        let names: Vec<_> = node
+            .find_compatible(&["riscv,clint0", "simple-bus"])
+            .map(|node| node.unwrap().name())
+            .collect();
+        assert_eq!(names, ["platform-bus@4000000", "soc", "clint@2000000"]);
+        assert!(node.find_compatible(&["arm,gic-400"]).next().is_none());
+
+        let test = node.find_compatible(&["syscon"]).next().unwrap().unwrap();
+        let compatible = test.get_prop("compatible").unwrap().deserialize::<StrSeq>();
+        assert!(compatible.iter().any(|s| s == "syscon"));
+        assert_eq!(test.compatible().unwrap().position(&["syscon"]), Some(2));
+
+        // `soc` lists `simple-bus` first, while `platform-bus` lists it second.
+        let best = node
+            .find_best_compatible(&["syscon", "simple-bus"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(best.name(), "soc");
+        let best = node
+            .find_best_compatible(&["sifive,test0", "syscon"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(best.name(), "test@100000");
+        assert!(
+            node.find_best_compatible(&["arm,gic-400"])
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{buildin::Node, de_mut::node::NodeItem, value::cpu::Cpu};
+
+/// Iterator over enabled harts under `/cpus`.
+pub struct CpuIter<'de> {
+    cpus: Option<Node<'de>>,
+    /// The last visited child of `/cpus`, iteration resumes after it.
+    last: Option<NodeItem<'de>>,
+}
+
+impl<'de> Node<'de> {
+    /// Get enabled harts, that is, children of `/cpus` with `device_type = "cpu"`
+    /// and an okay `status`.
+    pub fn cpus(&self) -> CpuIter<'de> {
+        CpuIter {
+            cpus: self.root().ok().and_then(|root| root.find("/cpus")),
+            last: None,
+        }
+    }
+}
+
+impl<'de> Iterator for CpuIter<'de> {
+    type Item = Cpu<'de>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let cpus = self.cpus.as_ref()?;
+        let nodes = match &self.last {
+            Some(last) => cpus.nodes_after(last),
+            None => cpus.nodes(),
+        };
+        for item in nodes {
+            let cpu = item
+                .try_deserialize::<Node>()
+                .ok()
+                .filter(|node| node.is_enabled())
+                .and_then(|node| Cpu::with_cpus(node, cpus.clone()));
+            self.last = Some(item);
+            if cpu.is_some() {
+                return cpu;
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dtb, DtbPtr, Status, buildin::Node, from_raw_mut, value::cpu::Cpu};
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[test]
+    fn test_cpus() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let mut cpus = node.cpus();
+        let cpu = cpus.next().unwrap();
+        // `cpu-map` is not a hart.
+        assert!(cpus.next().is_none());
+        assert_eq!(cpu.node().name(), "cpu@0");
+        assert_eq!(cpu.hart_id(), Some(0));
+        assert_eq!(cpu.status(), Some(Status::Okay));
+        assert!(cpu.isa().unwrap().starts_with("rv64imafdch_zic64b_"));
+        assert_eq!(cpu.isa_base(), Some("rv64i"));
+        let extensions = cpu.isa_extensions().unwrap();
+        assert_eq!(
+            extensions.iter().take(3).collect::<Vec<_>>(),
+            ["i", "m", "a"]
+        );
+        assert_eq!(cpu.mmu_type(), Some("riscv,sv57"));
+        assert_eq!(cpu.clock_frequency(), None);
+        // Inherited from `/cpus`.
+        assert_eq!(cpu.timebase_frequency(), Some(10_000_000));
+        let intc = cpu.interrupt_controller().unwrap();
+        assert_eq!(intc.get_prop("phandle").unwrap().deserialize::<u32>(), 2);
+
+        assert!(Cpu::new(node.find("/cpus/cpu@0").unwrap()).is_some());
+        assert!(Cpu::new(node.find("/cpus/cpu-map").unwrap()).is_none());
+    }
+    #[test]
+    fn test_disabled_cpus() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            cpus: Cpus,
+        }
+        #[derive(Serialize)]
+        #[serde(rename_all = "kebab-case")]
+        struct Cpus {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            clock_frequency: (u32, u32),
+            #[serde(rename = "cpu@0")]
+            cpu0: CpuNode,
+            #[serde(rename = "cpu@1")]
+            cpu1: CpuNode,
+            #[serde(rename = "cpu@2")]
+            cpu2: CpuNode,
+        }
+        #[derive(Serialize)]
+        struct CpuNode {
+            device_type: &'static str,
+            reg: (u32, u32),
+            status: &'static str,
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 512],
+        }
+        let mut buf = SerBuffer { data: [0; 512] };
+        let cpu = |reg, status| CpuNode {
+            device_type: "cpu",
+            reg: (0, reg),
+            status,
+        };
+        let root = Root {
+            cpus: Cpus {
+                address_cells: 2,
+                size_cells: 0,
+                clock_frequency: (1, 0),
+                cpu0: cpu(0, "disabled"),
+                cpu1: cpu(1, "okay"),
+                cpu2: cpu(2, "okay"),
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        let harts: Vec<_> = node.cpus().map(|cpu| cpu.hart_id().unwrap()).collect();
+        assert_eq!(harts, [1, 2]);
+        let cpu = node.cpus().next().unwrap();
+        assert_eq!(cpu.clock_frequency(), Some(1 << 32));
+        assert!(cpu.isa().is_none());
+        assert!(cpu.interrupt_controller().is_none());
+    }
+}
@@ -0,0 +1,164 @@
+use crate::buildin::{Node, WalkAction};
+use crate::error::Error;
+
+/// A driver matched against device tree nodes by `compatible` strings.
+///
+/// `C` is the context passed to every probe, like a device registry,
+/// and `E` is the error a probe may fail with.
+pub struct Driver<'a, C, E> {
+    /// Compatible strings this driver supports.
+    pub compatible: &'a [&'a str],
+    /// Called with every enabled node claimed by this driver.
+    pub probe: fn(&Node, &mut C) -> Result<(), E>,
+}
+
+impl<'de> Node<'de> {
+    /// Walk this subtree once and probe every enabled node with its best matching driver,
+    /// in tree order.
+    ///
+    /// Drivers are ranked by [`Compatible::position`](crate::Compatible::position),
+    /// and the first one in `drivers` wins a tie.
+    /// `unclaimed` is called for every enabled node that has `compatible` but no matching driver.
+    /// Nodes whose `status` is not okay are skipped together with their children.
+    ///
+    /// Probing stops at the first error, either from a probe or from walking the tree.
+    pub fn probe_drivers<C, E, F>(
+        &self,
+        drivers: &[Driver<C, E>],
+        context: &mut C,
+        mut unclaimed: F,
+    ) -> Result<(), E>
+    where
+        E: From<Error>,
+        F: FnMut(&Node<'de>),
+    {
+        let mut result = Ok(());
+        self.walk(|_, node| {
+            if !node.is_enabled() {
+                return WalkAction::SkipChildren;
+            }
+            let Some(compatible) = node.compatible() else {
+                return WalkAction::Continue;
+            };
+            let mut best: Option<(usize, &Driver<C, E>)> = None;
+            for driver in drivers {
+                if let Some(position) = compatible.position(driver.compatible)
+                    && best.is_none_or(|(best, _)| position < best)
+                {
+                    best = Some((position, driver));
+                }
+            }
+            match best {
+                Some((_, driver)) => {
+                    result = (driver.probe)(node, context);
+                    if result.is_err() {
+                        return WalkAction::Stop;
+                    }
+                }
+                None => unclaimed(node),
+            }
+            WalkAction::Continue
+        })?;
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Driver;
+    use crate::{Dtb, DtbPtr, buildin::Node, error::Error, from_raw_mut};
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/hifive-unmatched-a00.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+
+    #[derive(Debug)]
+    enum ProbeError {
+        #[allow(unused)]
+        Tree(Error),
+        Busy(String),
+    }
+    impl From<Error> for ProbeError {
+        fn from(e: Error) -> Self {
+            Self::Tree(e)
+        }
+    }
+    type Probed = Vec<(&'static str, String)>;
+
+    #[test]
+    fn test_probe_drivers() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let drivers: [Driver<Probed, ProbeError>; 3] = [
+            Driver {
+                compatible: &["sifive,uart0"],
+                probe: |node, probed| {
+                    probed.push(("uart", node.name().into()));
+                    Ok(())
+                },
+            },
+            Driver {
+                compatible: &["sifive,fu740-c000-uart"],
+                probe: |node, probed| {
+                    probed.push(("fu740-uart", node.name().into()));
+                    Ok(())
+                },
+            },
+            Driver {
+                compatible: &["sifive,i2c0", "sifive,spi0"],
+                probe: |node, probed| {
+                    probed.push(("i2c-spi", node.name().into()));
+                    Ok(())
+                },
+            },
+        ];
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let mut probed = Vec::new();
+        let mut unclaimed = Vec::new();
+        node.probe_drivers(&drivers, &mut probed, |node| unclaimed.push(node.name()))
+            .unwrap();
+
+        // The more specific compatible string wins, and disabled nodes are skipped.
+        assert_eq!(
+            probed,
+            [
+                ("fu740-uart", "serial@10010000".into()),
+                ("fu740-uart", "serial@10011000".into()),
+                ("i2c-spi", "i2c@10030000".into()),
+                ("i2c-spi", "spi@10040000".into()),
+                ("i2c-spi", "spi@10050000".into()),
+            ]
+        );
+        assert!(unclaimed.contains(&"temperature-sensor@4c"));
+        assert!(unclaimed.contains(&"flash@0"));
+        assert!(!unclaimed.contains(&"i2c@10031000"));
+        assert!(!unclaimed.contains(&"spi@10041000"));
+        // Nodes without `compatible` are not reported.
+        assert!(!unclaimed.contains(&"regulators"));
+
+        // A failed probe stops probing and returns its error.
+        let drivers: [Driver<Probed, ProbeError>; 1] = [Driver {
+            compatible: &["sifive,i2c0", "sifive,spi0"],
+            probe: |node, probed| match node.name() {
+                "spi@10040000" => Err(ProbeError::Busy(node.name().into())),
+                name => {
+                    probed.push(("i2c-spi", name.into()));
+                    Ok(())
+                }
+            },
+        }];
+        let mut probed = Vec::new();
+        let result = node.probe_drivers(&drivers, &mut probed, |_| {});
+        assert!(matches!(result, Err(ProbeError::Busy(name)) if name == "spi@10040000"));
+        assert_eq!(probed, [("i2c-spi", "i2c@10030000".into())]);
+    }
+}
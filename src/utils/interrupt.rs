@@ -0,0 +1,481 @@
+use crate::buildin::Node;
+use crate::de_mut::walk::MAX_DEPTH;
+
+/// Interrupts of a device, resolved to their interrupt controllers.
+///
+/// Each item is the controller node and the specifier cells,
+/// which stay big-endian as in the device tree.
+/// Interrupts sent to an interrupt nexus are translated with [`Node::map_interrupt`].
+pub struct Interrupts<'de> {
+    root: Node<'de>,
+    /// The interrupt parent for `interrupts`, or `None` for `interrupts-extended`.
+    parent: Option<Node<'de>>,
+    /// `reg` of the device, the child unit address for an interrupt nexus.
+    reg: &'de [u32],
+    data: &'de [u32],
+}
+
+/// Unit address of a device without `reg`, as many cells as an interrupt nexus may use.
+static ZERO_ADDRESS: [u32; 16] = [0; 16];
+
+impl<'de> Node<'de> {
+    /// Get the effective interrupt parent of this node.
+    ///
+    /// It follows `interrupt-parent`, or the parent node if it is absent,
+    /// until a node with `#interrupt-cells` is found.
+    /// Returns `None` if none is found within 64 steps, as in an `interrupt-parent` cycle.
+    pub fn interrupt_parent(&self) -> Option<Node<'de>> {
+        let mut child = self.clone();
+        for _ in 0..MAX_DEPTH {
+            let parent = match child.get_prop("interrupt-parent") {
+                Some(phandle) => self.find_by_phandle(phandle.try_deserialize::<u32>().ok()?)?,
+                None => child.parent()?,
+            };
+            if parent.get_prop("#interrupt-cells").is_some() {
+                return Some(parent);
+            }
+            child = parent;
+        }
+        None
+    }
+
+    /// Translate an interrupt through this interrupt nexus, and any nexus after it,
+    /// to the final interrupt controller and its specifier.
+    ///
+    /// `unit_address` and `specifier` are the child unit address and interrupt specifier,
+    /// sized by `#address-cells` and `#interrupt-cells` of this node.
+    /// They are masked by `interrupt-map-mask` and looked up in `interrupt-map`,
+    /// where the parent unit address and specifier are sized by `#address-cells` and
+    /// `#interrupt-cells` of each interrupt parent. A missing `#address-cells` is taken as 2
+    /// for the unit address of this node, as for `reg`, and as 0 for the interrupt parents,
+    /// as dtc and Linux do. A missing `#interrupt-cells` is taken as 0.
+    /// All cells stay big-endian as in the device tree.
+    ///
+    /// If this node is an interrupt controller, the specifier is returned unchanged.
+    pub fn map_interrupt<'a>(
+        &self,
+        unit_address: &'a [u32],
+        specifier: &'a [u32],
+    ) -> Option<(Node<'de>, &'a [u32])>
+    where
+        'de: 'a,
+    {
+        let mut nexus = self.clone();
+        let mut unit_address = unit_address;
+        let mut specifier = specifier;
+        let mut default_address_cells = 2;
+        loop {
+            if nexus.get_prop("interrupt-controller").is_some() {
+                return Some((nexus, specifier));
+            }
+            let address_cells = nexus.cells_or("#address-cells", default_address_cells)?;
+            let interrupt_cells = nexus.cells_or("#interrupt-cells", 0)?;
+            if unit_address.len() != address_cells || specifier.len() != interrupt_cells {
+                return None;
+            }
+            let child_cells = address_cells + interrupt_cells;
+            let mask = match nexus.get_prop("interrupt-map-mask") {
+                Some(mask) => Some(mask.cells()?).filter(|mask| mask.len() == child_cells)?,
+                None => &[],
+            };
+            let matches = |child: &[u32]| {
+                unit_address
+                    .iter()
+                    .chain(specifier)
+                    .zip(child)
+                    .enumerate()
+                    .all(|(i, (a, b))| (a ^ b) & mask.get(i).copied().unwrap_or(u32::MAX) == 0)
+            };
+            let mut map = nexus.get_prop("interrupt-map")?.cells()?;
+            let (parent, entry) = loop {
+                if map.len() <= child_cells {
+                    return None;
+                }
+                let (child, rest) = map.split_at(child_cells);
+                let parent = self.find_by_phandle(u32::from_be(rest[0]))?;
+                let parent_cells = parent.cells_or("#address-cells", 0)?
+                    + parent.cells_or("#interrupt-cells", 0)?;
+                if rest.len() < 1 + parent_cells {
+                    return None;
+                }
+                let (entry, rest) = rest[1..].split_at(parent_cells);
+                map = rest;
+                if matches(child) {
+                    break (parent, entry);
+                }
+            };
+            let parent_address_cells = parent.cells_or("#address-cells", 0)?;
+            (unit_address, specifier) = entry.split_at(parent_address_cells);
+            nexus = parent;
+            default_address_cells = 0;
+        }
+    }
+
+    /// Read a cell count property, or `default` if it is absent.
+    pub(crate) fn cells_or(&self, name: &str, default: usize) -> Option<usize> {
+        match self.get_prop(name) {
+            Some(prop) => Some(prop.try_deserialize::<u32>().ok()? as usize),
+            None => Some(default),
+        }
+    }
+
+    /// Get interrupts of this node from `interrupts-extended`, or `interrupts` if absent.
+    pub fn interrupts(&self) -> Option<Interrupts<'de>> {
+        let root = self.root().ok()?;
+        let reg = match self.get_prop("reg") {
+            Some(reg) => reg.cells()?,
+            None => &[],
+        };
+        if let Some(prop) = self.get_prop("interrupts-extended") {
+            return Some(Interrupts {
+                root,
+                parent: None,
+                reg,
+                data: prop.cells()?,
+            });
+        }
+        let data = self.get_prop("interrupts")?.cells()?;
+        Some(Interrupts {
+            root,
+            parent: Some(self.interrupt_parent()?),
+            reg,
+            data,
+        })
+    }
+}
+
+impl<'de> Iterator for Interrupts<'de> {
+    type Item = (Node<'de>, &'de [u32]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parent = match &self.parent {
+            Some(parent) => parent.clone(),
+            None => {
+                let (phandle, rest) = self.data.split_first()?;
+                self.data = rest;
+                self.root.find_by_phandle(u32::from_be(*phandle))?
+            }
+        };
+        let cells = parent
+            .get_prop("#interrupt-cells")?
+            .try_deserialize::<u32>()
+            .ok()? as usize;
+        if cells == 0 || self.data.len() < cells {
+            return None;
+        }
+        let (specifier, rest) = self.data.split_at(cells);
+        self.data = rest;
+        if parent.get_prop("interrupt-controller").is_some() {
+            return Some((parent, specifier));
+        }
+        // The parent is an interrupt nexus, which takes the unit address of the device
+        // as its `#address-cells` leading cells of `reg`, or zeros without `reg`.
+        let address_cells = parent.cells_or("#address-cells", 2)?;
+        let unit_address = match self.reg.get(..address_cells) {
+            Some(address) => address,
+            None => ZERO_ADDRESS.get(..address_cells)?,
+        };
+        parent.map_interrupt(unit_address, specifier)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dtb, DtbPtr, buildin::Node, from_raw_mut};
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[test]
+    fn test_interrupts() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let uart = node.find("/soc/serial@10000000").unwrap();
+        assert_eq!(uart.interrupt_parent().unwrap().name(), "plic@c000000");
+        let mut interrupts = uart.interrupts().unwrap();
+        let (controller, specifier) = interrupts.next().unwrap();
+        assert_eq!(controller.name(), "plic@c000000");
+        assert_eq!(specifier, [10u32.to_be()]);
+        assert!(interrupts.next().is_none());
+
+        let plic = node.find("/soc/plic@c000000").unwrap();
+        let interrupts: Vec<_> = plic
+            .interrupts()
+            .unwrap()
+            .map(|(controller, specifier)| (controller.name(), u32::from_be(specifier[0])))
+            .collect();
+        assert_eq!(
+            interrupts,
+            [("interrupt-controller", 0xb), ("interrupt-controller", 0x9)]
+        );
+
+        let cpu = node.find("/cpus/cpu@0").unwrap();
+        assert!(cpu.interrupts().is_none());
+    }
+    #[test]
+    fn test_inherited_interrupt_parent() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            intc: Intc,
+            bus: Bus,
+        }
+        #[derive(Serialize)]
+        struct Intc {
+            phandle: u32,
+            #[serde(rename = "interrupt-controller")]
+            interrupt_controller: (),
+            #[serde(rename = "#interrupt-cells")]
+            interrupt_cells: u32,
+        }
+        #[derive(Serialize)]
+        struct Bus {
+            #[serde(rename = "interrupt-parent")]
+            interrupt_parent: u32,
+            dev: Dev,
+        }
+        #[derive(Serialize)]
+        struct Dev {
+            interrupts: (u32, u32, u32, u32),
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 512],
+        }
+        let mut buf = SerBuffer { data: [0; 512] };
+        let root = Root {
+            intc: Intc {
+                phandle: 1,
+                interrupt_controller: (),
+                interrupt_cells: 2,
+            },
+            bus: Bus {
+                interrupt_parent: 1,
+                dev: Dev {
+                    interrupts: (5, 1, 6, 4),
+                },
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        // `dev` inherits `interrupt-parent` from `bus`.
+        let dev = node.find("/bus/dev").unwrap();
+        let interrupts: Vec<_> = dev
+            .interrupts()
+            .unwrap()
+            .map(|(controller, specifier)| {
+                let specifier: Vec<_> = specifier.iter().map(|x| u32::from_be(*x)).collect();
+                (controller.name(), specifier)
+            })
+            .collect();
+        assert_eq!(interrupts, [("intc", vec![5, 1]), ("intc", vec![6, 4])]);
+    }
+    #[test]
+    fn test_interrupt_parent_cycle() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            a: Link,
+            b: Link,
+        }
+        #[derive(Serialize)]
+        struct Link {
+            phandle: u32,
+            #[serde(rename = "interrupt-parent")]
+            interrupt_parent: u32,
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 512],
+        }
+        let mut buf = SerBuffer { data: [0; 512] };
+        let root = Root {
+            a: Link {
+                phandle: 1,
+                interrupt_parent: 2,
+            },
+            b: Link {
+                phandle: 2,
+                interrupt_parent: 1,
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        // `a` and `b` point at each other, and neither has `#interrupt-cells`.
+        let a = node.find("/a").unwrap();
+        assert!(a.interrupt_parent().is_none());
+    }
+    #[test]
+    fn test_nexus_interrupts() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            intc: Intc,
+            bus: Bus,
+            ext: Ext,
+        }
+        #[derive(Serialize)]
+        struct Intc {
+            phandle: u32,
+            #[serde(rename = "interrupt-controller")]
+            interrupt_controller: (),
+            #[serde(rename = "#interrupt-cells")]
+            interrupt_cells: u32,
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+        }
+        #[derive(Serialize)]
+        struct Bus {
+            phandle: u32,
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            #[serde(rename = "#interrupt-cells")]
+            interrupt_cells: u32,
+            #[serde(rename = "interrupt-map-mask")]
+            interrupt_map_mask: (u32, u32),
+            #[serde(rename = "interrupt-map")]
+            interrupt_map: (u32, u32, u32, u32, u32, u32, u32, u32),
+            #[serde(rename = "dev@1")]
+            dev: Dev,
+        }
+        #[derive(Serialize)]
+        struct Dev {
+            reg: u32,
+            interrupts: u32,
+        }
+        #[derive(Serialize)]
+        struct Ext {
+            #[serde(rename = "interrupts-extended")]
+            interrupts_extended: (u32, u32),
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 1024],
+        }
+        let mut buf = SerBuffer { data: [0; 1024] };
+        let root = Root {
+            intc: Intc {
+                phandle: 1,
+                interrupt_controller: (),
+                interrupt_cells: 1,
+                address_cells: 0,
+            },
+            bus: Bus {
+                phandle: 2,
+                address_cells: 1,
+                size_cells: 0,
+                interrupt_cells: 1,
+                interrupt_map_mask: (0xff, 0x7),
+                interrupt_map: (0x0, 1, 1, 5, 0x1, 1, 1, 6),
+                dev: Dev {
+                    reg: 1,
+                    interrupts: 1,
+                },
+            },
+            ext: Ext {
+                interrupts_extended: (2, 1),
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        // The nexus is not returned as a controller, and `reg` selects the map entry.
+        let dev = node.find("/bus/dev@1").unwrap();
+        assert_eq!(dev.interrupt_parent().unwrap().name(), "bus");
+        let mut interrupts = dev.interrupts().unwrap();
+        let (controller, specifier) = interrupts.next().unwrap();
+        assert_eq!(controller.name(), "intc");
+        assert_eq!(specifier, [6u32.to_be()]);
+        assert!(interrupts.next().is_none());
+        // Without `reg`, the unit address is zero.
+        let ext = node.find("/ext").unwrap();
+        let (controller, specifier) = ext.interrupts().unwrap().next().unwrap();
+        assert_eq!(controller.name(), "intc");
+        assert_eq!(specifier, [5u32.to_be()]);
+    }
+    #[test]
+    fn test_map_interrupt() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let pci = node.find("/soc/pci@30000000").unwrap();
+        let be = |cells: &[u32]| cells.iter().map(|x| x.to_be()).collect::<Vec<_>>();
+        // Device 1, INTA.
+        let (address, pin) = (be(&[0x800, 0, 0]), be(&[1]));
+        let (controller, specifier) = pci.map_interrupt(&address, &pin).unwrap();
+        assert_eq!(controller.name(), "plic@c000000");
+        assert_eq!(specifier, be(&[0x21]));
+        // Function bits are masked out by `interrupt-map-mask`.
+        let (address, pin) = (be(&[0x1900, 0, 0x1234]), be(&[4]));
+        let (_, specifier) = pci.map_interrupt(&address, &pin).unwrap();
+        assert_eq!(specifier, be(&[0x22]));
+        // Wrong specifier size.
+        assert!(pci.map_interrupt(&address, &[]).is_none());
+        // A controller returns the specifier unchanged.
+        let plic = node.find("/soc/plic@c000000").unwrap();
+        let pin = be(&[7]);
+        let (controller, specifier) = plic.map_interrupt(&[], &pin).unwrap();
+        assert_eq!(controller.name(), "plic@c000000");
+        assert_eq!(specifier, [7u32.to_be()]);
+    }
+    #[test]
+    fn test_map_pci_intx() {
+        const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/hifive-unmatched-a00.dtb");
+        const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let pcie = node.find("/soc/pcie@e00000000").unwrap();
+        // The PLIC has no `#address-cells`, so parent unit addresses in the map are empty.
+        let address = [0; 3];
+        for (pin, irq) in [(1u32, 0x39u32), (2, 0x3a), (3, 0x3b), (4, 0x3c)] {
+            let pin = [pin.to_be()];
+            let (controller, specifier) = pcie.map_interrupt(&address, &pin).unwrap();
+            assert_eq!(controller.name(), "interrupt-controller@c000000");
+            assert_eq!(specifier, [irq.to_be()]);
+        }
+    }
+}
@@ -1,6 +1,15 @@
 pub mod chosen;
+pub mod compatible;
+pub mod cpu;
+pub mod driver;
+pub mod interrupt;
+pub mod phandle;
+pub mod reserved_memory;
+pub mod status;
 
-use crate::buildin::{Node, StrSeq};
+use crate::buildin::{DmaRanges, Node, Ranges, Reg, WalkAction};
+use crate::de_mut::walk::Walker;
+use core::{fmt, ops::Range};
 
 impl<'de> Node<'de> {
     /// Try to get a node by a full-path.
@@ -45,8 +54,8 @@ impl<'de> Node<'de> {
                 if let Some(full_path) = aliases.get_prop(path) {
                     // As spec 3.3 said, this prop value should be one string,
                     // which is a full path ref to a node.
-                    let full_path = full_path.deserialize::<StrSeq>();
-                    return self.raw_find(full_path.iter().next().unwrap());
+                    let full_path = full_path.try_deserialize::<&str>().ok()?;
+                    return self.raw_find(full_path);
                 }
             }
             return None;
@@ -54,16 +63,109 @@ impl<'de> Node<'de> {
         self.raw_find(path)
     }
 
-    /// use depth-first search to traversal the tree, and exec func for each node
+    /// Translate the `index`-th region of this node's `reg` to a CPU physical address range.
+    ///
+    /// The `ranges` property of every ancestor bus is applied from the innermost outwards.
+    /// An empty `ranges` maps addresses unchanged, while an ancestor without `ranges`
+    /// makes the region not translatable and `None` is returned.
+    pub fn translate_reg(&self, index: usize) -> Option<Range<u64>> {
+        let reg = self.get_prop("reg")?.try_deserialize::<Reg>().ok()?;
+        let region = reg.iter().nth(index)?.0;
+        let (walker, depth) = self.locate()?;
+        (1..depth).rev().try_fold(region, |region, i| {
+            let bus = walker.ancestor(i).ok()?;
+            let ranges = bus.get_prop("ranges")?.try_deserialize::<Ranges>().ok()?;
+            ranges.translate(region)
+        })
+    }
+
+    /// Translate a CPU physical address range to the address this device must be programmed
+    /// with to access it by DMA.
+    ///
+    /// The `dma-ranges` property of every ancestor bus is applied from the outermost inwards.
+    /// A bus without `dma-ranges` is treated as a direct mapping.
+    /// Returns `None` if the range is not covered by some bus.
+    pub fn dma_address(&self, cpu: Range<u64>) -> Option<Range<u64>> {
+        self.fold_ancestors(cpu, |region, bus| match bus.get_prop("dma-ranges") {
+            Some(prop) => prop
+                .try_deserialize::<DmaRanges>()
+                .ok()?
+                .reverse_translate(region),
+            None => Some(region),
+        })
+    }
+
+    /// Check if DMA of this device is coherent with CPU caches,
+    /// that is, this node or any of its ancestors, including the root,
+    /// has `dma-coherent` property.
+    pub fn is_dma_coherent(&self) -> bool {
+        let coherent = |node: &Node| node.get_prop("dma-coherent").is_some();
+        coherent(self)
+            || self.root().is_ok_and(|root| coherent(&root))
+            || self
+                .fold_ancestors(false, |state, node| Some(state || coherent(node)))
+                .unwrap_or(false)
+    }
+
+    /// Get the parent node by searching from the root.
+    ///
+    /// Returns `None` for the root node, or a node nested deeper than 64 levels.
+    pub fn parent(&self) -> Option<Node<'de>> {
+        let (walker, depth) = self.locate()?;
+        walker.ancestor(depth.checked_sub(1)?).ok()
+    }
+
+    /// Write the full path of this node, like `/soc/serial@10000000`, to `w`.
+    ///
+    /// Returns an error for a node nested deeper than 64 levels.
+    pub fn path<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        let (walker, depth) = self.locate().ok_or(fmt::Error)?;
+        if depth == 0 {
+            return w.write_str("/");
+        }
+        for i in 1..=depth {
+            let node = walker.ancestor(i).map_err(|_| fmt::Error)?;
+            write!(w, "/{}", node.name())?;
+        }
+        Ok(())
+    }
+
+    /// Walk from the root to this node, and get the walker stopped on it with its depth.
+    ///
+    /// Returns `None` if the walk fails.
+    fn locate(&self) -> Option<(Walker<'de>, usize)> {
+        let mut walker = Walker::new(&self.root().ok()?);
+        let depth = walker.seek(self).ok()??;
+        Some((walker, depth))
+    }
+
+    /// Fold `state` with `f` over every ancestor of this node from the outermost inwards,
+    /// excluding the root and this node itself.
+    ///
+    /// Returns `None` if `f` returns `None`, or the walk from the root fails.
+    fn fold_ancestors<T>(
+        &self,
+        state: T,
+        mut f: impl FnMut(T, &Node<'de>) -> Option<T>,
+    ) -> Option<T> {
+        let (walker, depth) = self.locate()?;
+        (1..depth).try_fold(state, |state, i| f(state, &walker.ancestor(i).ok()?))
+    }
+
+    /// use depth-first search to traversal the tree, and exec func for each node.
+    ///
+    /// Use [`Node::path`] in `func` to report where a node lives,
+    /// and [`Node::walk`] to skip subtrees or stop early.
+    /// The traversal stops at a node nested deeper than 64 levels or a malformed
+    /// structure block; use [`Node::walk`] to get the error instead.
     pub fn search<F>(&self, func: &mut F)
     where
         F: FnMut(&Node),
     {
-        func(self);
-        for node in self.nodes() {
-            let node = node.deserialize::<Node>();
-            node.search(func);
-        }
+        let _ = self.walk(|_, node| {
+            func(node);
+            WalkAction::Continue
+        });
     }
 }
 
@@ -71,7 +173,7 @@ impl<'de> Node<'de> {
 mod tests {
     use crate::{
         Dtb, DtbPtr,
-        buildin::{Node, StrSeq},
+        buildin::{DmaRanges, Node, StrSeq},
         from_raw_mut,
     };
     const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/hifive-unmatched-a00.dtb");
@@ -132,4 +234,245 @@ mod tests {
             None => panic!("failed to find /chosen/stdout-path"),
         }
     }
+    #[test]
+    fn test_translate_reg() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            bus: Bus<Bus<Dev>>,
+            isolated: Isolated,
+        }
+        #[derive(Serialize)]
+        struct Bus<T> {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            ranges: (u32, u32, u32),
+            child: T,
+        }
+        #[derive(Serialize)]
+        struct Isolated {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            dev: Dev,
+        }
+        #[derive(Serialize)]
+        struct Dev {
+            reg: (u32, u32, u32, u32),
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 1024],
+        }
+        let mut buf = SerBuffer { data: [0; 1024] };
+        let root = Root {
+            address_cells: 1,
+            size_cells: 1,
+            bus: Bus {
+                address_cells: 1,
+                size_cells: 1,
+                ranges: (0x0, 0x1000_0000, 0x1000),
+                child: Bus {
+                    address_cells: 1,
+                    size_cells: 1,
+                    ranges: (0x100, 0x200, 0x100),
+                    child: Dev {
+                        reg: (0x110, 0x10, 0x180, 0x100),
+                    },
+                },
+            },
+            isolated: Isolated {
+                address_cells: 1,
+                size_cells: 1,
+                dev: Dev {
+                    reg: (0x0, 0x10, 0x20, 0x10),
+                },
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        let dev = node.find("/bus/child/child").unwrap();
+        assert_eq!(dev.translate_reg(0), Some(0x1000_0210..0x1000_0220));
+        // Out of the mapped window.
+        assert_eq!(dev.translate_reg(1), None);
+        assert_eq!(dev.translate_reg(2), None);
+        // The parent has no `ranges`, so it is not translatable.
+        let isolated = node.find("/isolated/dev").unwrap();
+        assert_eq!(isolated.translate_reg(0), None);
+    }
+    #[test]
+    fn test_dma_address() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            soc: Soc,
+            plain: Plain,
+        }
+        #[derive(Serialize)]
+        struct Soc {
+            #[serde(rename = "#address-cells")]
+            address_cells: u32,
+            #[serde(rename = "#size-cells")]
+            size_cells: u32,
+            #[serde(rename = "dma-ranges")]
+            dma_ranges: (u32, u32, u32),
+            #[serde(rename = "dma-coherent")]
+            dma_coherent: (),
+            dev: Dev,
+        }
+        #[derive(Serialize)]
+        struct Plain {
+            dev: Dev,
+        }
+        #[derive(Serialize)]
+        struct Dev {
+            reg: (u32, u32),
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 1024],
+        }
+        let mut buf = SerBuffer { data: [0; 1024] };
+        let root = Root {
+            address_cells: 1,
+            size_cells: 1,
+            soc: Soc {
+                address_cells: 1,
+                size_cells: 1,
+                dma_ranges: (0x0, 0x8000_0000, 0x1000_0000),
+                dma_coherent: (),
+                dev: Dev {
+                    reg: (0x1000, 0x100),
+                },
+            },
+            plain: Plain {
+                dev: Dev {
+                    reg: (0x2000, 0x100),
+                },
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        let soc = node.find("/soc").unwrap();
+        let dma_ranges = soc.get_prop("dma-ranges").unwrap();
+        let dma_ranges = dma_ranges.deserialize::<DmaRanges>();
+        assert!(!dma_ranges.is_identity());
+        assert_eq!(
+            dma_ranges.translate(0x1000..0x2000),
+            Some(0x8000_1000..0x8000_2000)
+        );
+
+        let dev = node.find("/soc/dev").unwrap();
+        assert_eq!(
+            dev.dma_address(0x8000_1000..0x8000_2000),
+            Some(0x1000..0x2000)
+        );
+        // The buffer is outside the DMA window of the bus.
+        assert_eq!(dev.dma_address(0x1000..0x2000), None);
+        assert!(dev.is_dma_coherent());
+        // Buses without `dma-ranges` map addresses directly.
+        let plain = node.find("/plain/dev").unwrap();
+        assert_eq!(
+            plain.dma_address(0x8000_1000..0x8000_2000),
+            Some(0x8000_1000..0x8000_2000)
+        );
+        assert!(!plain.is_dma_coherent());
+    }
+    #[test]
+    fn test_dma_coherent_root() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            #[serde(rename = "dma-coherent")]
+            dma_coherent: (),
+            bus: Bus,
+        }
+        #[derive(Serialize)]
+        struct Bus {
+            dev: Dev,
+        }
+        #[derive(Serialize)]
+        struct Dev {
+            reg: (u32, u32, u32, u32),
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 1024],
+        }
+        let mut buf = SerBuffer { data: [0; 1024] };
+        let root = Root {
+            dma_coherent: (),
+            bus: Bus {
+                dev: Dev {
+                    reg: (0, 0x1000, 0, 0x100),
+                },
+            },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        assert!(node.is_dma_coherent());
+        assert!(node.find("/bus").unwrap().is_dma_coherent());
+        assert!(node.find("/bus/dev").unwrap().is_dma_coherent());
+    }
+    #[test]
+    fn test_parent_and_path() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let serial = node.find("/soc/serial@10010000").unwrap();
+        let soc = serial.parent().unwrap();
+        assert_eq!(soc.name(), "soc");
+        assert_eq!(soc.parent().unwrap().name(), "");
+        assert!(node.parent().is_none());
+
+        let mut path = String::new();
+        serial.path(&mut path).unwrap();
+        assert_eq!(path, "/soc/serial@10010000");
+        path.clear();
+        node.path(&mut path).unwrap();
+        assert_eq!(path, "/");
+
+        // Every node found by `search` can be located again by its path.
+        let mut count = 0;
+        node.search(&mut |n: &Node| {
+            let mut path = String::new();
+            n.path(&mut path).unwrap();
+            assert!(node.find(&path).unwrap().is_same(n));
+            count += 1;
+        });
+        assert_eq!(count, 70);
+    }
 }
@@ -0,0 +1,164 @@
+use crate::buildin::{Node, WalkAction};
+use crate::error::Error;
+
+/// An index from phandles to nodes, stored in a slice provided by the caller.
+///
+/// Build it once with [`Node::phandle_index`] to avoid rescanning the whole
+/// structure block on every [`Node::find_by_phandle`].
+pub struct PhandleIndex<'a, 'de> {
+    entries: &'a [Option<(u32, Node<'de>)>],
+}
+
+impl<'de> Node<'de> {
+    /// Get the phandle of this node, from `phandle` or legacy `linux,phandle` property.
+    pub fn phandle(&self) -> Option<u32> {
+        self.get_prop("phandle")
+            .or_else(|| self.get_prop("linux,phandle"))?
+            .try_deserialize::<u32>()
+            .ok()
+    }
+
+    /// Try to get a node by its phandle, searching the whole device tree.
+    pub fn find_by_phandle(&self, phandle: u32) -> Option<Node<'de>> {
+        let mut found = None;
+        self.root()
+            .ok()?
+            .walk(|_, node| {
+                if node.phandle() != Some(phandle) {
+                    return WalkAction::Continue;
+                }
+                found = Some(node.clone());
+                WalkAction::Stop
+            })
+            .ok()?;
+        found
+    }
+
+    /// Build a phandle index of the whole device tree in `buf`.
+    ///
+    /// If `buf` is too small to hold every node with a phandle, returns
+    /// [`ErrorType::BufferTooSmall`](crate::error::ErrorType::BufferTooSmall)
+    /// with the required length.
+    pub fn phandle_index<'a>(
+        &self,
+        buf: &'a mut [Option<(u32, Node<'de>)>],
+    ) -> Result<PhandleIndex<'a, 'de>, Error> {
+        // Count every node with a phandle, even after `buf` is full.
+        let mut len = 0;
+        self.root()?.walk(|_, node| {
+            if let Some(phandle) = node.phandle() {
+                if let Some(slot) = buf.get_mut(len) {
+                    *slot = Some((phandle, node.clone()));
+                }
+                len += 1;
+            }
+            WalkAction::Continue
+        })?;
+        if len > buf.len() {
+            return Err(Error::buffer_too_small(len));
+        }
+        let entries = &mut buf[..len];
+        entries.sort_unstable_by_key(|entry| entry.as_ref().map(|(phandle, _)| *phandle));
+        Ok(PhandleIndex { entries })
+    }
+}
+
+impl<'de> PhandleIndex<'_, 'de> {
+    /// Get a node by its phandle.
+    pub fn get(&self, phandle: u32) -> Option<&Node<'de>> {
+        let i = self
+            .entries
+            .binary_search_by_key(&Some(phandle), |entry| entry.as_ref().map(|(p, _)| *p))
+            .ok()?;
+        self.entries[i].as_ref().map(|(_, node)| node)
+    }
+
+    /// Number of nodes with a phandle.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no node in the device tree has a phandle.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Dtb, DtbPtr,
+        buildin::Node,
+        error::{Error, ErrorType},
+        from_raw_mut,
+    };
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[test]
+    fn test_find_by_phandle() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let plic = node.find_by_phandle(3).unwrap();
+        assert_eq!(plic.name(), "plic@c000000");
+        assert_eq!(plic.phandle(), Some(3));
+        // Search starts from the root even when called on a child node.
+        let cpu = plic.find_by_phandle(1).unwrap();
+        assert_eq!(cpu.name(), "cpu@0");
+        assert!(node.find_by_phandle(0x100).is_none());
+
+        let mut buf = [const { None }; 8];
+        let index = node.phandle_index(&mut buf).unwrap();
+        assert_eq!(index.len(), 4);
+        assert_eq!(index.get(2).unwrap().name(), "interrupt-controller");
+        assert_eq!(index.get(4).unwrap().name(), "test@100000");
+        assert!(index.get(5).is_none());
+
+        let mut small = [const { None }; 2];
+        assert!(matches!(
+            node.phandle_index(&mut small),
+            Err(Error::Typed {
+                error_type: ErrorType::BufferTooSmall { required_length: 4 },
+                ..
+            })
+        ));
+    }
+    #[test]
+    fn test_legacy_phandle() {
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct Root {
+            legacy: Legacy,
+        }
+        #[derive(Serialize)]
+        struct Legacy {
+            #[serde(rename = "linux,phandle")]
+            phandle: u32,
+        }
+        #[repr(align(8))]
+        struct SerBuffer {
+            pub data: [u8; 256],
+        }
+        let mut buf = SerBuffer { data: [0; 256] };
+        let root = Root {
+            legacy: Legacy { phandle: 7 },
+        };
+        crate::ser::to_dtb(&root, &[], &mut buf.data).unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        assert_eq!(node.find_by_phandle(7).unwrap().name(), "legacy");
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{
+    Compatible,
+    buildin::{Node, Reg},
+    de_mut::walk::Walker,
+};
+use core::ops::Range;
+
+/// A child of `/reserved-memory`, describing a reserved memory region.
+pub struct ReservedMemory<'de> {
+    node: Node<'de>,
+}
+
+/// Iterator over enabled children of `/reserved-memory`.
+pub struct ReservedMemoryIter<'de> {
+    walker: Option<Walker<'de>>,
+    enter: bool,
+}
+
+impl<'de> ReservedMemory<'de> {
+    /// Get the node of this reserved memory region.
+    pub fn node(&self) -> &Node<'de> {
+        &self.node
+    }
+
+    /// Get the static regions, from `reg`.
+    pub fn reg(&self) -> Option<Reg<'de>> {
+        self.node.get_prop("reg")?.try_deserialize().ok()
+    }
+
+    /// Get the size of a dynamically allocated region, from `size`.
+    pub fn size(&self) -> Option<u64> {
+        self.node.get_prop("size")?.cells_u64()
+    }
+
+    /// Get the alignment of a dynamically allocated region, from `alignment`.
+    pub fn alignment(&self) -> Option<u64> {
+        self.node.get_prop("alignment")?.cells_u64()
+    }
+
+    /// Get the ranges a dynamically allocated region may be placed in, from `alloc-ranges`.
+    pub fn alloc_ranges(&self) -> Option<Reg<'de>> {
+        self.node.get_prop("alloc-ranges")?.try_deserialize().ok()
+    }
+
+    /// Check if the operating system must not map this region, that is, `no-map` is present.
+    pub fn no_map(&self) -> bool {
+        self.node.get_prop("no-map").is_some()
+    }
+
+    /// Check if the operating system may use this region when the owner does not,
+    /// that is, `reusable` is present.
+    pub fn reusable(&self) -> bool {
+        self.node.get_prop("reusable").is_some()
+    }
+
+    /// Get the `compatible` property of this region.
+    pub fn compatible(&self) -> Option<Compatible<'de>> {
+        self.node.compatible()
+    }
+}
+
+impl<'de> Node<'de> {
+    /// Get enabled children of `/reserved-memory`.
+    pub fn reserved_memory(&self) -> ReservedMemoryIter<'de> {
+        let parent = self
+            .root()
+            .ok()
+            .and_then(|root| root.find("/reserved-memory"));
+        ReservedMemoryIter {
+            walker: parent.map(|parent| Walker::new(&parent)),
+            enter: true,
+        }
+    }
+
+    /// Compute free RAM ranges in `buf`, sorted by address.
+    ///
+    /// It takes the `reg` regions of every enabled `device_type = "memory"` node, then
+    /// subtracts static regions of `/reserved-memory`, the memory reservation block and
+    /// `blob`, the physical range the device tree blob itself is loaded at.
+    /// Dynamically allocated reserved regions, described by `size` and `alloc-ranges`
+    /// instead of `reg`, are not placed yet, and are ignored.
+    ///
+    /// Returns `None` if `buf` is too small, or a memory node has no valid `reg`.
+    pub fn free_memory<'a>(
+        &self,
+        blob: Range<u64>,
+        buf: &'a mut [Range<u64>],
+    ) -> Option<&'a [Range<u64>]> {
+        let root = self.root().ok()?;
+        let mut len = 0;
+        for node in root
+            .nodes()
+            .filter_map(|node| node.try_deserialize::<Node>().ok())
+        {
+            let device_type = node.get_prop("device_type");
+            if device_type.and_then(|prop| prop.try_deserialize().ok()) != Some("memory")
+                || !node.is_enabled()
+            {
+                continue;
+            }
+            let reg = node.get_prop("reg")?.try_deserialize::<Reg>().ok()?;
+            for region in reg.iter().filter(|region| !region.0.is_empty()) {
+                *buf.get_mut(len)? = region.0;
+                len += 1;
+            }
+        }
+        for reserved in root.reserved_memory() {
+            if let Some(reg) = reserved.reg() {
+                for region in reg.iter() {
+                    subtract(buf, &mut len, region.0)?;
+                }
+            }
+        }
+        for (address, size) in root.memory_reservations() {
+            subtract(buf, &mut len, address..address.saturating_add(size))?;
+        }
+        subtract(buf, &mut len, blob)?;
+
+        let free = &mut buf[..len];
+        free.sort_unstable_by_key(|range| range.start);
+        Some(free)
+    }
+}
+
+/// Remove `hole` from the first `len` ranges in `buf`.
+///
+/// Returns `None` if a range is split in two, and `buf` has no room for the second one.
+fn subtract(buf: &mut [Range<u64>], len: &mut usize, hole: Range<u64>) -> Option<()> {
+    let mut i = 0;
+    while i < *len {
+        let range = buf[i].clone();
+        if range.end <= hole.start || hole.end <= range.start {
+            i += 1;
+            continue;
+        }
+        let left = range.start..hole.start;
+        let right = hole.end..range.end;
+        match (left.is_empty(), right.is_empty()) {
+            (true, true) => {
+                *len -= 1;
+                buf.swap(i, *len);
+                continue;
+            }
+            (false, true) => buf[i] = left,
+            (true, false) => buf[i] = right,
+            (false, false) => {
+                *buf.get_mut(*len)? = right;
+                buf[i] = left;
+                *len += 1;
+            }
+        }
+        i += 1;
+    }
+    Some(())
+}
+
+impl<'de> Iterator for ReservedMemoryIter<'de> {
+    type Item = ReservedMemory<'de>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let walker = self.walker.as_mut()?;
+        loop {
+            let (depth, node) = walker.next_node(self.enter)?.ok()?;
+            // Only enter `/reserved-memory` itself.
+            self.enter = depth == 0;
+            if depth == 1 && node.is_enabled() {
+                return Some(ReservedMemory { node });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dtb, DtbPtr, buildin::Node, from_raw_mut};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct Root {
+        #[serde(rename = "#address-cells")]
+        address_cells: u32,
+        #[serde(rename = "#size-cells")]
+        size_cells: u32,
+        #[serde(rename = "memory@80000000")]
+        memory: Memory,
+        #[serde(rename = "reserved-memory")]
+        reserved_memory: ReservedMemory,
+    }
+    #[derive(Serialize)]
+    struct Memory {
+        device_type: &'static str,
+        reg: (u32, u32, u32, u32),
+    }
+    #[derive(Serialize)]
+    struct ReservedMemory {
+        #[serde(rename = "#address-cells")]
+        address_cells: u32,
+        #[serde(rename = "#size-cells")]
+        size_cells: u32,
+        ranges: (),
+        #[serde(rename = "mmode_resv0@80000000")]
+        firmware: Firmware,
+        #[serde(rename = "linux,cma")]
+        cma: Cma,
+        #[serde(rename = "framebuffer@88000000")]
+        framebuffer: Framebuffer,
+    }
+    #[derive(Serialize)]
+    struct Firmware {
+        reg: (u32, u32, u32, u32),
+        #[serde(rename = "no-map")]
+        no_map: (),
+    }
+    #[derive(Serialize)]
+    struct Cma {
+        compatible: &'static str,
+        reusable: (),
+        size: (u32, u32),
+        alignment: (u32, u32),
+        #[serde(rename = "alloc-ranges")]
+        alloc_ranges: (u32, u32, u32, u32),
+    }
+    #[derive(Serialize)]
+    struct Framebuffer {
+        reg: (u32, u32, u32, u32),
+        status: &'static str,
+    }
+    #[repr(align(8))]
+    struct SerBuffer {
+        pub data: [u8; 1024],
+    }
+
+    #[test]
+    fn test_reserved_memory() {
+        let mut buf = SerBuffer { data: [0; 1024] };
+        let root = Root {
+            address_cells: 2,
+            size_cells: 2,
+            memory: Memory {
+                device_type: "memory",
+                reg: (0, 0x8000_0000, 0, 0x1000_0000),
+            },
+            reserved_memory: ReservedMemory {
+                address_cells: 2,
+                size_cells: 2,
+                ranges: (),
+                firmware: Firmware {
+                    reg: (0, 0x8000_0000, 0, 0x4_0000),
+                    no_map: (),
+                },
+                cma: Cma {
+                    compatible: "shared-dma-pool",
+                    reusable: (),
+                    size: (0, 0x100_0000),
+                    alignment: (0, 0x1000),
+                    alloc_ranges: (0, 0x8800_0000, 0, 0x800_0000),
+                },
+                framebuffer: Framebuffer {
+                    reg: (0, 0x8800_0000, 0, 0x10_0000),
+                    status: "okay",
+                },
+            },
+        };
+        crate::ser::to_dtb_with_reservations(
+            &root,
+            &[],
+            &[(0x8f00_0000, 0x100_0000)],
+            &mut buf.data,
+        )
+        .unwrap();
+        let ptr = DtbPtr::from_raw(buf.data.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+        let node: Node = from_raw_mut(&dtb).unwrap();
+
+        let mut reserved = node.reserved_memory();
+        let firmware = reserved.next().unwrap();
+        assert_eq!(firmware.node().name(), "mmode_resv0@80000000");
+        assert!(firmware.no_map());
+        assert!(!firmware.reusable());
+        let region = firmware.reg().unwrap().iter().next().unwrap().0;
+        assert_eq!(region, 0x8000_0000..0x8004_0000);
+        let cma = reserved.next().unwrap();
+        assert!(cma.reg().is_none());
+        assert!(cma.reusable());
+        assert_eq!(cma.size(), Some(0x100_0000));
+        assert_eq!(cma.alignment(), Some(0x1000));
+        let region = cma.alloc_ranges().unwrap().iter().next().unwrap().0;
+        assert_eq!(region, 0x8800_0000..0x9000_0000);
+        assert_eq!(
+            cma.compatible().unwrap().position(&["shared-dma-pool"]),
+            Some(0)
+        );
+        assert_eq!(
+            reserved.next().unwrap().node().name(),
+            "framebuffer@88000000"
+        );
+        assert!(reserved.next().is_none());
+
+        let mut free = [const { 0..0 }; 8];
+        let free = node.free_memory(0..0, &mut free).unwrap();
+        assert_eq!(free, [0x8004_0000..0x8800_0000, 0x8810_0000..0x8f00_0000]);
+
+        let mut free = [const { 0..0 }; 8];
+        let free = node
+            .free_memory(0x8200_0000..0x8201_0000, &mut free)
+            .unwrap();
+        assert_eq!(
+            free,
+            [
+                0x8004_0000..0x8200_0000,
+                0x8201_0000..0x8800_0000,
+                0x8810_0000..0x8f00_0000
+            ]
+        );
+
+        let mut small = [const { 0..0 }; 1];
+        assert!(node.free_memory(0..0, &mut small).is_none());
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{
+    Status,
+    buildin::{Node, WalkAction},
+    error::Error,
+};
+
+impl<'de> Node<'de> {
+    /// Get the status of this node, `Okay` if `status` property is absent.
+    ///
+    /// Returns `None` if `status` is not a valid status string.
+    pub fn status(&self) -> Option<Status<'de>> {
+        match self.get_prop("status") {
+            Some(status) => status.try_deserialize().ok(),
+            None => Some(Status::Okay),
+        }
+    }
+
+    /// Check if this node is operational, that is, its status is `Okay`.
+    pub fn is_enabled(&self) -> bool {
+        self.status().is_some_and(|status| status.is_okay())
+    }
+
+    /// Like [`Node::search`], but nodes that are not enabled are skipped
+    /// together with their children.
+    pub fn search_enabled<F>(&self, func: &mut F) -> Result<(), Error>
+    where
+        F: FnMut(&Node),
+    {
+        self.walk(|_, node| {
+            if !node.is_enabled() {
+                return WalkAction::SkipChildren;
+            }
+            func(node);
+            WalkAction::Continue
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Dtb, DtbPtr, Status,
+        buildin::{Node, NodeSeq},
+        from_raw_mut,
+    };
+    use serde::Deserialize;
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/hifive-unmatched-a00.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[test]
+    fn test_status() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let i2c = node.find("/soc/i2c@10031000").unwrap();
+        assert_eq!(i2c.status(), Some(Status::Disabled));
+        assert!(!i2c.is_enabled());
+        let i2c = node.find("/soc/i2c@10030000").unwrap();
+        assert_eq!(i2c.status(), Some(Status::Okay));
+        // No `status` property.
+        assert!(node.find("/soc").unwrap().is_enabled());
+
+        let soc = node.find("/soc").unwrap();
+        assert_eq!(soc.nodes().count() - soc.enabled_nodes().count(), 2);
+        assert!(
+            soc.enabled_nodes()
+                .all(|node| node.get_full_name() != "spi@10041000")
+        );
+
+        #[derive(Deserialize)]
+        struct Soc<'a> {
+            i2c: NodeSeq<'a>,
+            spi: NodeSeq<'a>,
+        }
+        let soc: Soc = soc.deserialize();
+        assert_eq!(soc.i2c.len(), 2);
+        let enabled: Vec<_> = soc
+            .i2c
+            .iter_enabled()
+            .map(|node| node.at().to_owned())
+            .collect();
+        assert_eq!(enabled, ["10030000"]);
+        let enabled: Vec<_> = soc
+            .spi
+            .iter_enabled()
+            .map(|node| node.at().to_owned())
+            .collect();
+        assert_eq!(enabled, ["10040000", "10050000"]);
+
+        let mut all = 0;
+        node.search(&mut |_| all += 1);
+        let mut enabled = 0;
+        node.search_enabled(&mut |node| {
+            assert!(node.is_enabled());
+            enabled += 1;
+        })
+        .unwrap();
+        // Both disabled nodes have no children.
+        assert_eq!(all - enabled, 2);
+    }
+
+    #[test]
+    fn test_parse_status() {
+        assert_eq!(Status::parse("ok"), Some(Status::Okay));
+        assert_eq!(Status::parse("reserved"), Some(Status::Reserved));
+        assert_eq!(Status::parse("fail"), Some(Status::Fail));
+        assert_eq!(
+            Status::parse("fail-no-clock"),
+            Some(Status::FailCondition("no-clock"))
+        );
+        assert_eq!(Status::parse("broken"), None);
+    }
+}
@@ -14,6 +14,15 @@ impl<'a> Compatible<'a> {
             remaining: self.data,
         }
     }
+
+    /// Get the position in this list of the first string that is also in `compatibles`.
+    ///
+    /// The list goes from the most specific to the most general,
+    /// so a smaller position means a better match.
+    pub fn position(&self, compatibles: &[&str]) -> Option<usize> {
+        self.iter()
+            .position(|item| compatibles.iter().any(|s| s.as_bytes() == item))
+    }
 }
 
 impl<'de: 'a, 'a> Deserialize<'de> for Compatible<'a> {
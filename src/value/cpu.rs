@@ -1,4 +1,123 @@
-// /// ISA independent processor description.
-// pub struct CpuMut<'a> {
-//     memory: &'a mut [u8],
-// }
+use crate::{
+    Status,
+    buildin::{Node, StrSeq},
+    de_mut::node::PropItem,
+    value::riscv_isa::RiscvIsa,
+};
+
+/// A RISC-V hart, described by a `cpu` node under `/cpus`.
+///
+/// Properties that may be shared by all harts, like `timebase-frequency`,
+/// fall back to the value on `/cpus` when the `cpu` node does not have them.
+#[derive(Clone)]
+pub struct Cpu<'de> {
+    pub(crate) node: Node<'de>,
+    pub(crate) cpus: Node<'de>,
+}
+
+impl<'de> Cpu<'de> {
+    /// Get the hart of a `cpu` node, returns `None` if `device_type` is not `"cpu"`.
+    pub fn new(node: Node<'de>) -> Option<Self> {
+        if !is_cpu(&node) {
+            return None;
+        }
+        let cpus = node.parent()?;
+        Some(Self { node, cpus })
+    }
+
+    /// Like [`Cpu::new`], but with the known parent `cpus` instead of searching for it.
+    pub(crate) fn with_cpus(node: Node<'de>, cpus: Node<'de>) -> Option<Self> {
+        is_cpu(&node).then_some(Self { node, cpus })
+    }
+
+    /// Get the `cpu` node of this hart.
+    pub fn node(&self) -> &Node<'de> {
+        &self.node
+    }
+
+    /// Get the hart ID, from the first `#address-cells` cells of `reg`,
+    /// where `#address-cells` is declared by `/cpus`.
+    pub fn hart_id(&self) -> Option<usize> {
+        let address_cells = self.cpus.cells_or("#address-cells", 2)?;
+        let id = match *self.node.get_prop("reg")?.cells()?.get(..address_cells)? {
+            [id] => u32::from_be(id) as u64,
+            [high, low] => (u32::from_be(high) as u64) << 32 | u32::from_be(low) as u64,
+            _ => return None,
+        };
+        usize::try_from(id).ok()
+    }
+
+    /// Get the status of this hart, `Okay` if `status` is absent.
+    pub fn status(&self) -> Option<Status<'de>> {
+        self.node.status()
+    }
+
+    /// Get the ISA string, from `riscv,isa`.
+    pub fn isa(&self) -> Option<&'de str> {
+        self.get_prop("riscv,isa")?.try_deserialize().ok()
+    }
+
+    /// Get the base ISA, like `rv64i`, from `riscv,isa-base`.
+    pub fn isa_base(&self) -> Option<&'de str> {
+        self.get_prop("riscv,isa-base")?.try_deserialize().ok()
+    }
+
+    /// Get the list of ISA extensions, from `riscv,isa-extensions`.
+    pub fn isa_extensions(&self) -> Option<StrSeq<'de>> {
+        self.get_prop("riscv,isa-extensions")?
+            .try_deserialize()
+            .ok()
+    }
+
+    /// Get the parsed ISA, from `riscv,isa-base` and `riscv,isa-extensions`,
+    /// or `riscv,isa` if they are absent.
+    pub fn riscv_isa(&self) -> Option<RiscvIsa<'de>> {
+        match (self.isa_base(), self.get_prop("riscv,isa-extensions")) {
+            (Some(base), Some(extensions)) => {
+                RiscvIsa::from_base_and_extensions(base, extensions.try_deserialize().ok()?)
+            }
+            _ => RiscvIsa::parse(self.isa()?),
+        }
+    }
+
+    /// Get the type of MMU, like `riscv,sv39`, from `mmu-type`.
+    pub fn mmu_type(&self) -> Option<&'de str> {
+        self.get_prop("mmu-type")?.try_deserialize().ok()
+    }
+
+    /// Get the clock frequency in Hz, from `clock-frequency`.
+    pub fn clock_frequency(&self) -> Option<u64> {
+        self.get_prop("clock-frequency")?.cells_u64()
+    }
+
+    /// Get the frequency of the `time` CSR in Hz, from `timebase-frequency`.
+    pub fn timebase_frequency(&self) -> Option<u64> {
+        self.get_prop("timebase-frequency")?.cells_u64()
+    }
+
+    /// Get the local interrupt controller of this hart,
+    /// the child node compatible with `riscv,cpu-intc`.
+    pub fn interrupt_controller(&self) -> Option<Node<'de>> {
+        self.node
+            .nodes()
+            .filter_map(|item| item.try_deserialize::<Node>().ok())
+            .find(|node| {
+                node.compatible()
+                    .is_some_and(|compatible| compatible.position(&["riscv,cpu-intc"]).is_some())
+            })
+    }
+
+    /// Get a property of this hart, or of `/cpus` if the hart does not have it.
+    fn get_prop(&self, name: &str) -> Option<PropItem<'de>> {
+        self.node
+            .get_prop(name)
+            .or_else(|| self.cpus.get_prop(name))
+    }
+}
+
+/// Check if `device_type` of `node` is `"cpu"`.
+fn is_cpu(node: &Node) -> bool {
+    node.get_prop("device_type")
+        .and_then(|prop| prop.try_deserialize().ok())
+        == Some("cpu")
+}
@@ -1,4 +1,6 @@
 pub mod compatible;
 pub mod cpu;
+pub mod riscv_isa;
 pub mod riscv_pmu;
+pub mod status;
 mod tree;
@@ -0,0 +1,414 @@
+use core::{fmt, marker::PhantomData};
+use serde::{
+    Deserialize,
+    de::{self, MapAccess, Visitor},
+};
+
+/// ISA of a RISC-V hart, like `rv64imafdc_zicsr_zifencei_svpbmt_zba`.
+///
+/// It is parsed from `riscv,isa`, or from `riscv,isa-base` and `riscv,isa-extensions`.
+/// Deserializing a property takes the former, and deserializing a `cpu` node prefers the latter.
+///
+/// The XLEN and single-letter extensions are decoded on construction. Multi-letter extensions
+/// stay in the property and are decoded on iteration, so no allocation is needed.
+/// Names are lowercase, as the binding requires.
+#[derive(Clone, Copy, Debug)]
+pub struct RiscvIsa<'a> {
+    xlen: u32,
+    /// Bit `n` is set if the single-letter extension `'a' + n` is present.
+    letters: u32,
+    source: Source<'a>,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Source<'a> {
+    /// `'_'` separated extensions of `riscv,isa`, after the XLEN.
+    Isa(&'a str),
+    /// `'\0'` separated list of `riscv,isa-extensions`.
+    List(&'a [u8]),
+}
+
+/// A multi-letter extension, like `zicsr` or `svpbmt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extension<'a> {
+    /// Name of the extension, starting with `z`, `s` or `x`.
+    pub name: &'a str,
+    /// Major and minor version, if specified.
+    pub version: Option<(u32, u32)>,
+}
+
+/// Iterator over multi-letter extensions.
+#[derive(Clone)]
+pub struct Extensions<'a> {
+    source: Source<'a>,
+}
+
+impl<'a> RiscvIsa<'a> {
+    /// Parse an ISA string of `riscv,isa`, returns `None` if it is not valid.
+    pub fn parse(isa: &'a str) -> Option<Self> {
+        let (xlen, extensions) = parse_base(isa)?;
+        let mut letters = 0;
+        for token in extensions.split('_') {
+            let (single, multi) = split_token(token);
+            if token.is_empty() || (!multi.is_empty() && parse_extension(multi).is_none()) {
+                return None;
+            }
+            letters |= parse_letters(single)?;
+        }
+        Some(Self::new(xlen, letters, Source::Isa(extensions)))
+    }
+
+    /// Parse a base ISA of `riscv,isa-base` like `rv64i`, and the raw value of
+    /// `riscv,isa-extensions`, that is, `'\0'` separated extension names.
+    ///
+    /// Returns `None` if any of them is not valid.
+    pub fn from_base_and_extensions(base: &'a str, extensions: &'a [u8]) -> Option<Self> {
+        let (xlen, base) = parse_base(base)?;
+        let mut letters = parse_letters(base)?;
+        // Each name ends with `'\0'`.
+        let names = extensions.strip_suffix(b"\0").unwrap_or(extensions);
+        for name in names.split(|c| *c == b'\0') {
+            let name = core::str::from_utf8(name).ok()?;
+            if name.len() == 1 {
+                letters |= parse_letters(name)?;
+            } else {
+                parse_extension(name)?;
+            }
+        }
+        Some(Self::new(xlen, letters, Source::List(extensions)))
+    }
+
+    fn new(xlen: u32, mut letters: u32, source: Source<'a>) -> Self {
+        // `G` is short for `IMAFD_Zicsr_Zifencei`.
+        if letters & bit(b'g') != 0 {
+            letters |= bit(b'i') | bit(b'm') | bit(b'a') | bit(b'f') | bit(b'd');
+        }
+        Self {
+            xlen,
+            letters,
+            source,
+        }
+    }
+
+    /// Get the width of integer registers, 32, 64 or 128.
+    pub fn xlen(&self) -> u32 {
+        self.xlen
+    }
+
+    /// Check if the base ISA is `RV32E` or `RV64E`, with 16 integer registers.
+    pub fn is_embedded(&self) -> bool {
+        self.letters & bit(b'e') != 0
+    }
+
+    /// Check if an extension is present, by a single-letter or multi-letter lowercase name.
+    pub fn has(&self, name: &str) -> bool {
+        match name.as_bytes() {
+            [c @ b'a'..=b'z'] => self.letters & bit(*c) != 0,
+            _ => {
+                (self.letters & bit(b'g') != 0 && matches!(name, "zicsr" | "zifencei"))
+                    || self.extensions().any(|extension| extension.name == name)
+            }
+        }
+    }
+
+    /// Get single-letter extensions in alphabetical order, including the base `i` or `e`.
+    pub fn letters(&self) -> impl Iterator<Item = char> + 'static {
+        let letters = self.letters;
+        (b'a'..=b'z')
+            .filter(move |c| letters & bit(*c) != 0)
+            .map(char::from)
+    }
+
+    /// Get multi-letter extensions in the order they are listed.
+    pub fn extensions(&self) -> Extensions<'a> {
+        Extensions {
+            source: self.source,
+        }
+    }
+}
+
+fn bit(letter: u8) -> u32 {
+    1 << (letter - b'a')
+}
+
+/// Split `rv64` off a base ISA, returns the XLEN and the rest starting with the base letter.
+fn parse_base(isa: &str) -> Option<(u32, &str)> {
+    let isa = isa.strip_prefix("rv")?;
+    let (xlen, rest) = [(32, "32"), (64, "64"), (128, "128")]
+        .into_iter()
+        .find_map(|(xlen, prefix)| Some((xlen, isa.strip_prefix(prefix)?)))?;
+    matches!(rest.as_bytes().first(), Some(b'i' | b'e' | b'g')).then_some((xlen, rest))
+}
+
+/// Split a `'_'` separated token into single-letter extensions and a multi-letter extension.
+///
+/// A `z` extension may follow single-letter extensions directly, like `imaczicsr`, but `s` and
+/// `x` extensions only start at a `'_'`. Elsewhere `s` and `x` are single letters, as in legacy
+/// strings like `rv64imafdcsu`.
+fn split_token(token: &str) -> (&str, &str) {
+    let start = if token.starts_with(['s', 'x']) {
+        0
+    } else {
+        token.find('z').unwrap_or(token.len())
+    };
+    token.split_at(start)
+}
+
+/// Parse single-letter extensions, each may have a version like `2p1`.
+fn parse_letters(letters: &str) -> Option<u32> {
+    let mut ans = 0;
+    let mut rest = letters.as_bytes();
+    while let [c, tail @ ..] = rest {
+        if !c.is_ascii_lowercase() || *c == b'z' {
+            return None;
+        }
+        ans |= bit(*c);
+        let major = tail.iter().take_while(|c| c.is_ascii_digit()).count();
+        rest = match &tail[major..] {
+            [b'p', minor @ ..] if major > 0 && minor.first().is_some_and(u8::is_ascii_digit) => {
+                &minor[minor.iter().take_while(|c| c.is_ascii_digit()).count()..]
+            }
+            tail => tail,
+        };
+    }
+    Some(ans)
+}
+
+/// Parse a multi-letter extension with an optional version, like `zicsr` or `zba1p0`.
+fn parse_extension(extension: &str) -> Option<Extension<'_>> {
+    fn split_digits(s: &str) -> (&str, &str) {
+        s.split_at(s.trim_end_matches(|c: char| c.is_ascii_digit()).len())
+    }
+    let (head, minor) = split_digits(extension);
+    let (name, version) = match head.strip_suffix('p').map(split_digits) {
+        _ if minor.is_empty() => (extension, None),
+        Some((name, major)) if !major.is_empty() => (name, Some((major, minor))),
+        _ => (head, Some((minor, "0"))),
+    };
+    let valid = name.len() > 1
+        && matches!(name.as_bytes()[0], b'z' | b's' | b'x')
+        && name
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+    let version = match version {
+        Some((major, minor)) => Some((major.parse().ok()?, minor.parse().ok()?)),
+        None => None,
+    };
+    valid.then_some(Extension { name, version })
+}
+
+impl<'a> Iterator for Extensions<'a> {
+    type Item = Extension<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match &mut self.source {
+                Source::Isa(isa) => {
+                    let current: &'a str = isa;
+                    if current.is_empty() {
+                        return None;
+                    }
+                    let (token, rest) = current.split_once('_').unwrap_or((current, ""));
+                    *isa = rest;
+                    split_token(token).1
+                }
+                Source::List(list) => {
+                    let current: &'a [u8] = list;
+                    if current.is_empty() {
+                        return None;
+                    }
+                    let len = current.iter().position(|c| *c == b'\0');
+                    let (entry, rest) = current.split_at(len.unwrap_or(current.len()));
+                    *list = rest.get(1..).unwrap_or_default();
+                    let name = core::str::from_utf8(entry).ok()?;
+                    // Single-letter extensions are skipped.
+                    if name.len() > 1 { name } else { "" }
+                }
+            };
+            if !entry.is_empty() {
+                return parse_extension(entry);
+            }
+        }
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for RiscvIsa<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct IsaVisitor<'de, 'a>(PhantomData<&'de ()>, PhantomData<RiscvIsa<'a>>);
+        impl<'de: 'a, 'a> Visitor<'de> for IsaVisitor<'de, 'a> {
+            type Value = RiscvIsa<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    formatter,
+                    "riscv,isa string, or cpu node with riscv,isa-base and riscv,isa-extensions"
+                )
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let v = trim_nul(v);
+                RiscvIsa::parse(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+
+            fn visit_borrowed_bytes<E>(self, v: &'de [u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                // `from_raw` takes a property of unknown type as bytes.
+                let v = core::str::from_utf8(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))?;
+                self.visit_borrowed_str(v)
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let (mut isa, mut base, mut extensions) = (None, None, None);
+                while let Some(key) = map.next_key::<&'de str>()? {
+                    match key {
+                        "riscv,isa" => isa = Some(trim_nul(map.next_value()?)),
+                        "riscv,isa-base" => base = Some(trim_nul(map.next_value()?)),
+                        "riscv,isa-extensions" => extensions = Some(map.next_value::<&'de [u8]>()?),
+                        _ => {
+                            map.next_value::<de::IgnoredAny>()?;
+                        }
+                    }
+                }
+                let ans = match (base, extensions, isa) {
+                    (Some(base), Some(extensions), _) => {
+                        RiscvIsa::from_base_and_extensions(base, extensions)
+                    }
+                    (_, _, Some(isa)) => RiscvIsa::parse(isa),
+                    _ => return Err(de::Error::missing_field("riscv,isa")),
+                };
+                ans.ok_or_else(|| de::Error::invalid_value(de::Unexpected::Map, &self))
+            }
+        }
+        deserializer.deserialize_any(IsaVisitor(PhantomData, PhantomData))
+    }
+}
+
+/// Strip the trailing NUL that `from_raw` keeps in string properties.
+fn trim_nul(s: &str) -> &str {
+    s.strip_suffix('\0').unwrap_or(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Extension, RiscvIsa};
+    use crate::{Dtb, DtbPtr, buildin::Node, from_raw_mut, value::cpu::Cpu};
+
+    #[test]
+    fn test_parse_isa() {
+        let isa = RiscvIsa::parse("rv64imafdc_zicsr_zifencei_svpbmt_zba").unwrap();
+        assert_eq!(isa.xlen(), 64);
+        assert!(!isa.is_embedded());
+        assert_eq!(isa.letters().collect::<String>(), "acdfim");
+        let names: Vec<_> = isa.extensions().map(|extension| extension.name).collect();
+        assert_eq!(names, ["zicsr", "zifencei", "svpbmt", "zba"]);
+        assert!(isa.has("c") && isa.has("zba") && isa.has("svpbmt"));
+        assert!(!isa.has("v") && !isa.has("zicbom") && !isa.has("zb"));
+
+        // Versions, and a multi-letter extension right after single-letter ones.
+        let isa = RiscvIsa::parse("rv32e2p0m2p0c_zmmul1p0_xfoo2").unwrap();
+        assert_eq!(isa.xlen(), 32);
+        assert!(isa.is_embedded());
+        assert_eq!(isa.letters().collect::<String>(), "cem");
+        let isa = RiscvIsa::parse("rv32imazicsr2p0_xfoo2").unwrap();
+        assert_eq!(
+            isa.extensions().collect::<Vec<_>>(),
+            [
+                Extension {
+                    name: "zicsr",
+                    version: Some((2, 0)),
+                },
+                Extension {
+                    name: "xfoo",
+                    version: Some((2, 0)),
+                },
+            ]
+        );
+
+        // `G` implies `IMAFD_Zicsr_Zifencei`.
+        let isa = RiscvIsa::parse("rv64gc").unwrap();
+        assert_eq!(isa.letters().collect::<String>(), "acdfgim");
+        assert!(isa.has("zicsr") && isa.has("zifencei"));
+        assert_eq!(isa.extensions().count(), 0);
+
+        // Legacy strings have single-letter `S` and `U`, multi-letter `s` extensions follow `_`.
+        let isa = RiscvIsa::parse("rv64imafdcsu").unwrap();
+        assert_eq!(isa.letters().collect::<String>(), "acdfimsu");
+        assert_eq!(isa.extensions().count(), 0);
+        let isa = RiscvIsa::parse("rv64imacsu_svpbmt").unwrap();
+        assert_eq!(isa.letters().collect::<String>(), "acimsu");
+        let names: Vec<_> = isa.extensions().map(|extension| extension.name).collect();
+        assert_eq!(names, ["svpbmt"]);
+
+        for invalid in [
+            "",
+            "rv64",
+            "rv16i",
+            "rv64m",
+            "RV64IMAC",
+            "rv64imac_",
+            "rv64imac__zicsr",
+            "rv64i_z",
+            "rv64i_zi-csr",
+        ] {
+            assert!(RiscvIsa::parse(invalid).is_none(), "{invalid}");
+        }
+
+        let isa = RiscvIsa::from_base_and_extensions("rv64i", b"i\0m\0a\0zicbom\0svadu\0").unwrap();
+        assert_eq!(isa.letters().collect::<String>(), "aim");
+        assert!(isa.has("zicbom") && isa.has("svadu") && !isa.has("c"));
+        assert_eq!(isa.extensions().count(), 2);
+        assert!(RiscvIsa::from_base_and_extensions("rv64i", b"i\0\0m\0").is_none());
+        assert!(RiscvIsa::from_base_and_extensions("rv64i", b"i\0z\0").is_none());
+        assert!(RiscvIsa::from_base_and_extensions("rv64im", b"i\0").is_some());
+    }
+
+    const RAW_DEVICE_TREE: &[u8] = include_bytes!("../../examples/qemu-virt.dtb");
+    const BUFFER_SIZE: usize = RAW_DEVICE_TREE.len();
+    #[test]
+    fn test_deserialize_isa() {
+        #[repr(align(8))]
+        struct AlignedBuffer {
+            pub data: [u8; RAW_DEVICE_TREE.len()],
+        }
+        let mut aligned_data: Box<AlignedBuffer> = Box::new(AlignedBuffer {
+            data: [0; BUFFER_SIZE],
+        });
+        aligned_data.data[..BUFFER_SIZE].clone_from_slice(RAW_DEVICE_TREE);
+        let mut slice = aligned_data.data.to_vec();
+        let ptr = DtbPtr::from_raw(slice.as_mut_ptr()).unwrap();
+        let dtb = Dtb::from(ptr).share();
+
+        let node: Node = from_raw_mut(&dtb).unwrap();
+        let cpu = node.find("/cpus/cpu@0").unwrap();
+        // From `riscv,isa`.
+        let isa = cpu.get_prop("riscv,isa").unwrap().deserialize::<RiscvIsa>();
+        assert_eq!(isa.letters().collect::<String>(), "acdfhim");
+        assert!(isa.has("zicbom") && isa.has("svadu") && !isa.has("v"));
+        // From `riscv,isa-base` and `riscv,isa-extensions`.
+        let from_list = cpu.deserialize::<RiscvIsa>();
+        assert_eq!(from_list.xlen(), 64);
+        assert!(from_list.has("zicbom") && from_list.has("h") && !from_list.has("v"));
+        assert!(from_list.extensions().eq(isa.extensions()));
+        let from_cpu = Cpu::new(cpu.clone()).unwrap().riscv_isa().unwrap();
+        assert!(from_cpu.letters().eq(isa.letters()));
+        // A node without any ISA property.
+        assert!(
+            node.find("/cpus/cpu-map")
+                .unwrap()
+                .try_deserialize::<RiscvIsa>()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use core::{fmt, marker::PhantomData};
+use serde::{Deserialize, de::Visitor};
+
+/// Operational status of a device, the value of `status` property.
+///
+/// A node without `status` is operational, as if it is `Okay`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status<'a> {
+    /// The device is operational, `"okay"` or the legacy `"ok"`.
+    Okay,
+    /// The device is not operational at present, but it might become so in the future.
+    Disabled,
+    /// The device is operational but should not be used, usually because it is controlled
+    /// by other software.
+    Reserved,
+    /// The device is not operational because of a serious error, and is unlikely to become so.
+    Fail,
+    /// Same as `Fail`, with the device specific error condition following `"fail-"`.
+    FailCondition(&'a str),
+}
+
+impl<'a> Status<'a> {
+    /// Parse a `status` string, returns `None` if it is not a valid status.
+    pub fn parse(value: &'a str) -> Option<Self> {
+        match value {
+            "okay" | "ok" => Some(Self::Okay),
+            "disabled" => Some(Self::Disabled),
+            "reserved" => Some(Self::Reserved),
+            "fail" => Some(Self::Fail),
+            _ => value.strip_prefix("fail-").map(Self::FailCondition),
+        }
+    }
+
+    /// Check if the device is operational and can be used.
+    pub fn is_okay(&self) -> bool {
+        *self == Self::Okay
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Status<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct StatusVisitor<'de, 'a>(PhantomData<&'de ()>, PhantomData<Status<'a>>);
+        impl<'de: 'a, 'a> Visitor<'de> for StatusVisitor<'de, 'a> {
+            type Value = Status<'a>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(formatter, "okay, disabled, reserved, fail or fail-sss")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                // `from_raw` keeps the trailing NUL of the string property.
+                let v = v.strip_suffix('\0').unwrap_or(v);
+                Status::parse(v)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+        }
+        deserializer.deserialize_str(StatusVisitor(PhantomData, PhantomData))
+    }
+}
@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use serde_device_tree::Compatible;
+use serde_device_tree::{Compatible, Status, value::riscv_isa::RiscvIsa};
 
 #[derive(Debug, Deserialize)]
 struct Tree<'a> {
@@ -11,7 +11,7 @@ struct Tree<'a> {
     #[allow(unused)]
     compatible: Compatible<'a>,
     chosen: Option<Chosen<'a>>,
-    cpus: Cpus,
+    cpus: Cpus<'a>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -22,10 +22,48 @@ struct Chosen<'a> {
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "kebab-case")]
-struct Cpus {
+struct Cpus<'a> {
     timebase_frequency: u32,
     #[serde(rename = "u-boot,dm-spl")]
     u_boot_dm_spl: bool,
+    #[serde(rename = "cpu@0", borrow)]
+    cpu0: MonitorCpu<'a>,
+    #[serde(rename = "cpu@1")]
+    cpu1: Cpu<'a>,
+    #[serde(rename = "cpu@2")]
+    cpu2: RiscvIsa<'a>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MonitorCpu<'a> {
+    #[serde(borrow)]
+    status: Status<'a>,
+    #[serde(rename = "riscv,isa")]
+    isa: RiscvIsa<'a>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Cpu<'a> {
+    device_type: DeviceType,
+    #[serde(rename = "riscv,isa", borrow)]
+    isa: RiscvIsa<'a>,
+    #[serde(rename = "mmu-type")]
+    mmu_type: MmuType,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum DeviceType {
+    Cpu,
+    Memory,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+enum MmuType {
+    #[serde(rename = "riscv,sv39")]
+    Sv39,
+    #[serde(rename = "riscv,sv48")]
+    Sv48,
 }
 
 const RAW_DEVICE_TREE: &[u8] = include_bytes!("../examples/hifive-unmatched-a00.dtb");
@@ -56,4 +94,10 @@ fn hifive_unmatched() {
     }
     assert_eq!(t.cpus.timebase_frequency, 1000000);
     assert!(t.cpus.u_boot_dm_spl);
+    assert_eq!(t.cpus.cpu0.status, Status::Okay);
+    assert_eq!(t.cpus.cpu1.device_type, DeviceType::Cpu);
+    assert_eq!(t.cpus.cpu1.mmu_type, MmuType::Sv39);
+    assert_eq!(t.cpus.cpu0.isa.letters().collect::<String>(), "acim");
+    assert_eq!(t.cpus.cpu1.isa.letters().collect::<String>(), "acdfim");
+    assert_eq!(t.cpus.cpu2.letters().collect::<String>(), "acdfim");
 }